@@ -2,35 +2,419 @@
 extern crate manifest_dir_macros;
 
 fn main() {
+    println!(manifest_dir!());
+    println!("{} {}", package_name!(), package_version!());
+
     println!(path!("Cargo.toml"));
     println!(path!("src/lib.rs"));
     println!(path!("src", "lib.rs"));
     println!(path!("src", "lib.rs", "/bin"));
     println!(path!("/usr"));
+    println!(path!("src/"));
+    println!(path!("src", concat = ("lib", ".", "rs")));
+    println!(path!(r"src", r#"lib.rs"#));
+    println!(concat!(path!("src", "lib"), ".rs"));
+    println!(path!(base = manifest, "src"));
+    println!(path!(base = "CARGO_MANIFEST_DIR", "src", "lib.rs"));
+    println!(env_or_manifest_path!("ASSET_ROOT", "icons", "x.png"));
+    println!(getenv_path!("CARGO_MANIFEST_DIR"));
+    println!(getenv_path!(default = "/opt/sdk", "SDK_ROOT"));
+    println!(exist_getenv_path!("CARGO_MANIFEST_DIR"));
+    println!(try_canonicalize_path!("src", "..", "Cargo.toml"));
+    println!(try_canonicalize_path!("target", "not-built-yet.bin"));
+    println!(realpath_relative!("src", "..", "src", "lib.rs"));
+    println!(trim_trailing_separator!("src/"));
+    println!(trim_trailing_separator!("/"));
+    println!(add_extension!("src", "lib.rs", "bak"));
+    println!(add_extension!(".env", "gz"));
+    println!(concat!(path_concat!("src", "lib"), ".rs"));
+    println!(display_path!("src", "lib.rs"));
+
+    println!(raw_path!("src", "lib.rs"));
+    println!(exist_raw_path!("/"));
 
     println!(exist_path!("Cargo.toml"));
     println!(directory_path!("src"));
     println!(not_directory_path!("Cargo.toml"));
+    println!(not_existing_directory_path!("Cargo.toml"));
+    println!(not_existing_directory_path!("Cargo.lock.bak"));
     println!(file_path!("Cargo.toml"));
+    println!(file_path!(msg = "{} must be checked in", "Cargo.toml"));
+    println!(file_path!(default = path!("Cargo.toml"), "primary.bin"));
+    println!(directory_path!(default = "/tmp", "Cargo.toml"));
+
+    let _: &std::path::Path = static_path!("Cargo.toml");
+    println!("{:?}", exist_static_path!("Cargo.toml"));
+    println!("{:?}", directory_static_path!("src"));
+    println!("{:?}", not_directory_static_path!("Cargo.toml"));
+    println!("{:?}", file_static_path!("Cargo.toml"));
+
+    let _: String = path_string!("src", "lib.rs");
+    let _: String = file_name_string!("src/lib.rs");
+    let _: String = file_stem_string!("src/lib.rs");
+    let _: String = extension_string!("src/lib.rs");
+    let _: String = parent_string!("src/lib.rs");
+
+    const CARGO_TOML: Option<&str> = try_exist_path!("Cargo.toml");
+    const CARGO_LOCK_BAK: Option<&str> = try_exist_path!("Cargo.lock.bak");
+    println!("{:?} {:?}", CARGO_TOML, CARGO_LOCK_BAK);
+
+    println!(first_existing_path!("Cargo.lock.bak", ("src", "lib.rs"), "Cargo.toml"));
+    println!(first_existing_path!(default = "/usr", "Cargo.lock.bak"));
+
+    const SEARCH_PATHS: &[&str] = unique_paths!("src", ("src", "..", "src"), "examples");
+    println!("{:?}", SEARCH_PATHS);
+    println!(path_if_exists_else!(("config", "local.toml"), "Cargo.toml"));
 
     println!(relative_path!("Cargo.toml"));
     println!(directory_relative_path!("src"));
     println!(not_directory_relative_path!("Cargo.toml"));
     println!(file_relative_path!("Cargo.toml"));
+    assert_eq!(relative_path!(prefix = "/static", "css/app.css"), "/static/css/app.css");
+    assert_eq!(relative_path!(prefix = "/static", "."), "/static");
+    assert_eq!(file_relative_path!(prefix = "/static", "src", "lib.rs"), "/static/src/lib.rs");
+
+    assert_eq!(relative_path_from!("src", "src/lib.rs"), "lib.rs");
+    assert_eq!(relative_path_from!("src", "Cargo.toml"), path_concat!("..", "Cargo.toml"));
+    assert_eq!(relative_path_from!(("src", "a", "b"), "src/c"), path_concat!("..", "..", "c"));
+    assert_eq!(relative_path_from!("src", "./src"), ".");
 
     println!(get_file_name!("src/lib.rs"));
     println!(get_file_name!(default = "main.rs", "/"));
+    let _: &std::ffi::OsStr = get_file_name_os!("src/lib.rs");
+    println!("{:?}", get_file_name_os!(default = "main.rs", "/"));
+    println!("{:?}", file_names!("Cargo.toml", ("src", "lib.rs"), "src/functions.rs"));
+    println!("{:?}", file_names!(default = "", "Cargo.toml", "/"));
     println!(get_file_stem!("src/lib.rs"));
     println!(get_file_stem!(default = "lib", "/"));
+    println!(get_file_stem!(strip_all, "archive.tar.gz"));
     println!(get_extension!("src/lib.rs"));
     println!(get_extension!(default = "rs", "src/lib"));
+    assert_eq!(compound_extension!("archive.tar.gz"), "tar.gz");
+    assert_eq!(compound_extension!("src/lib.rs"), "rs");
+    assert_eq!(compound_extension!(compounds = ["user.js"], "prefs.user.js"), "user.js");
+    println!(extension_or_empty!("src/lib.rs"));
+    println!(extension_or_empty!("LICENSE"));
+    println!(first_component!("src/lib.rs"));
+    println!(first_component!(skip_root, "/usr/lib"));
+    println!(last_component!("src/.."));
+    println!(last_component!(default = ".", ""));
+    println!(file_name_with_extension!("src/lib.rs", "html"));
+    println!(file_name_with_extension!(default = "index.html", "/", "html"));
     println!(get_parent!("src/lib.rs"));
     println!(get_parent!(default = "/home", "/"));
+    println!(parent_dir_path!("src/lib.rs"));
+    println!(parent_dir_path!(default = "/home", "/"));
+
+    const _: () = assert!(depth!("src/lib.rs") == 2);
+    const _: () = assert!(depth!("/usr/lib") == 2);
+    const _: () = assert!(depth!("./src", "lib.rs") == 2);
+    #[cfg(windows)]
+    const _: () = assert!(depth!(r"C:\Windows\System32") == 2);
+
+    const _: () = assert!(is_relative!("foo"));
+    const _: () = assert!(!is_absolute!("foo", "bar"));
+    #[cfg(unix)]
+    const _: () = assert!(is_absolute!("/usr"));
+
+    println!(filename_len_range!(min = 1, max = 255, "Cargo.toml"));
+    println!(filename_len_range!(min = 10, max = 10, "Cargo.toml"));
+
+    println!("{:?}", newest_in_dir!("src"));
+    println!("{:?}", newest_in_dir!(recursive, "src"));
+
+    println!(collapse_repeats!("src/src/lib.rs"));
+    println!(collapse_repeats!("src", "lib.rs"));
+
+    println!(lower_ext_path!("Assets/LOGO.PNG"));
+    println!(lower_ext_path!("Assets", "Logo.Tar.GZ"));
+    println!(lower_ext_path!("LICENSE"));
+
+    println!(trim_extension!("src/lib.rs"));
+    println!(trim_extension!("archive.tar.gz"));
+
+    const SPLIT_EXTENSION: (&str, &str) = split_extension!("src/lib.rs");
+    println!("{:?}", SPLIT_EXTENSION);
+
+    const _: () = assert!(path_eq!("src", ("src", "..", "src")));
+    println!("{}", path_eq!(ignore_case, "Cargo.toml", "cargo.toml"));
+
+    println!(dirname!("src/lib.rs"));
+    println!(dirname!("lib.rs"));
+    println!(basename!("src/lib.rs"));
+    println!(basename!("src/lib.rs", ".rs"));
+    println!(basename!(("src", "lib.rs"), ".rs"));
+
+    const _: () = assert!(relative_depth!("src", "lib.rs") == 2);
+    const _: () = assert!(relative_depth!("a/../../x") == -1);
+    assert_eq!(relative_depth!("a/../../x").abs(), 1);
+
+    println!(verbatim_path!("src/lib.rs"));
+    println!(short_path!("src/lib.rs"));
+
+    #[cfg(all(
+        any(target_os = "linux", target_os = "macos", target_os = "windows"),
+        any(target_arch = "x86_64", target_arch = "aarch64")
+    ))]
+    {
+        const ICON: &str = os_path!(
+            os = ["linux", "macos", "windows"],
+            arch = ["x86_64", "aarch64"],
+            "assets",
+            "{target_os}-{target_arch}",
+            "icon.png"
+        );
+        assert!(std::path::Path::new(ICON).ends_with(format!(
+            "assets/{}-{}/icon.png",
+            std::env::consts::OS,
+            std::env::consts::ARCH
+        )));
+    }
+
+    println!(assert_under_manifest!("src/../Cargo.toml"));
+    println!(assert_under_manifest!("plugins", "./foo/../bar"));
+
+    println!(nearest_ancestor_containing!("Cargo.toml"));
+    println!(nearest_ancestor_containing!(default = "/", "a-marker-which-does-not-exist"));
+
+    const _: () = assert!(!has_bom!("Cargo.toml"));
+    println!(bom_kind!("Cargo.toml"));
+
+    const CARGO_TOML_CONTENT: &str = include_trimmed!("Cargo.toml");
+    assert!(CARGO_TOML_CONTENT.starts_with("[package]"));
+    assert!(!CARGO_TOML_CONTENT.ends_with('\n'));
+    assert!(read_to_string!(strip_bom, "Cargo.toml").starts_with("[package]"));
+    println!("{:?}", path_list_from_file!("examples/paths.txt"));
+
+    let cargo_toml_bytes = byte_array!("Cargo.toml");
+    println!("{}", cargo_toml_bytes.len());
+
+    println!(up_path!(levels = 1, "shared/assets"));
+    println!(up_path!(levels = 2, "shared/assets"));
+
+    println!(index_path!("src"));
+    println!(index_path!(index = "lib.rs", "src"));
+    println!(index_path!(index = "lib.rs", "Cargo.toml"));
+
+    println!("{}", tree_file_count!("src"));
+    println!("{}", tree_file_count!(max_depth = 1, "."));
+
+    const _: () = assert!(dir_index!("tests/fixtures/dir_index", ("css", "app.css")) == 0);
+    const _: () = assert!(dir_index!("tests/fixtures/dir_index", ("css", "theme.css")) == 1);
+    const _: () = assert!(dir_index!("tests/fixtures/dir_index", "index.html") == 2);
+    const _: () = assert!(dir_index!("tests/fixtures/dir_index", ("js", "app.js")) == 3);
+
+    const _: () = assert!(!is_empty_dir!("src"));
+    const _: () = assert!(!is_empty_dir!("Cargo.toml"));
+
+    assert_no_case_collisions!(recursive, "src");
+    assert_no_case_collisions!(recursive, max_depth = 2, "src");
+
+    println!("{:?}", read_dir!("src"));
+    println!("{}", child_count!("src"));
+    println!("{}", child_count!(files_only, "src"));
+    println!("{}", child_count!(dirs_only, "."));
+
+    const _: () = assert!(extension_is!("src/lib.rs", "rs"));
+    const _: () = assert!(extension_is!(ignore_case, "src", "lib.rs", "RS"));
+    const _: () = assert!(!extension_is!("LICENSE", "rs"));
+
+    const _: () = assert!(extension_denied!(deny = ["exe", "dll", "so"], "uploads/file.EXE"));
+    const _: () = assert!(!extension_denied!(deny = ["exe", "dll", "so"], "src/lib.rs"));
+    const _: () = assert!(!extension_denied!(deny = ["exe"], "LICENSE"));
+    println!(assert_not_denied!(deny = ["exe", "dll", "so"], "src/lib.rs"));
+
+    println!(assert_extension!("rs", "src/lib.rs"));
+    println!(assert_extension!(ignore_case, ["toml", "json"], "Cargo.TOML"));
+
+    {
+        use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+
+        // The token can be decoded back to the relative path, whose separators are always `/`.
+        assert_eq!(URL_SAFE_NO_PAD.decode(path_token!("src/lib.rs")).unwrap(), b"src/lib.rs");
+        assert_eq!(URL_SAFE_NO_PAD.decode(path_token!("src", "lib.rs")).unwrap(), b"src/lib.rs");
+    }
+
+    const MODIFIED_TIME: u64 = modified_time!("Cargo.toml");
+    assert_eq!(
+        MODIFIED_TIME,
+        std::fs::metadata(path!("Cargo.toml"))
+            .unwrap()
+            .modified()
+            .unwrap()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+    );
+    println!("{}", modified_time!(default = 0, "Cargo.lock.bak"));
+
+    println!("{}", symlink_component_count!("src/lib.rs"));
+
+    // `tests/fixtures/symlink/link-dir` links to `dir`, and `dir/link-file.txt` links to `file.txt`.
+    #[cfg(unix)]
+    const _: () = assert!(
+        symlink_component_count!("tests/fixtures/symlink/link-dir/link-file.txt")
+            == symlink_component_count!(".") + 2
+    );
+
+    const CARGO_TOML_LINK_PAIR: (&str, &str) = link_pair!("Cargo.toml");
+    assert_eq!(CARGO_TOML_LINK_PAIR.0, CARGO_TOML_LINK_PAIR.1);
+
+    #[cfg(unix)]
+    {
+        const LINK_DIR_PAIR: (&str, &str) = link_pair!(strict, "tests/fixtures/symlink/link-dir");
+        assert_eq!(
+            LINK_DIR_PAIR,
+            (path!("tests/fixtures/symlink/link-dir"), path!("tests/fixtures/symlink/dir"))
+        );
+    }
+
+    println!("{:?}", breadcrumbs!("src/bin/tool.rs"));
+    println!("{:?}", breadcrumbs!("Cargo.toml"));
+
+    // Macros which output numbers emit suffixed literals, so they can be used in `const` items.
+    const DEPTH: usize = depth!("src/lib.rs");
+    const SYMLINK_COMPONENT_COUNT: usize = symlink_component_count!("src/lib.rs");
+    const NEWEST: (&str, u64) = newest_in_dir!("src");
+    println!("{} {} {:?}", DEPTH, SYMLINK_COMPONENT_COUNT, NEWEST);
 
     #[cfg(feature = "mime_guess")]
     {
-        println!(mime_guess!("src/lib.rs"));
+        assert_eq!(mime_guess!("src/lib.rs"), "text/x-rust");
         println!(mime_guess!(default = "application/octet-stream", "Cargo.lock"));
+        assert_eq!(mime_guess!("Cargo.toml"), "text/x-toml");
+        assert_eq!(
+            mime_guess!(default = "application/octet-stream", "LICENSE"),
+            "application/octet-stream"
+        );
+        assert_eq!(mime_guess!("Makefile"), "text/x-makefile");
+        assert_eq!(mime_guess!(".gitignore"), "text/plain");
+        println!("{:?}", mime_guess_all!("src/lib.rs"));
+        println!("{:?}", mime_guess_all!(default = "application/octet-stream", "LICENSE"));
+
+        // `mime_guess_typed!` needs `mime = "0.3"` in the dependencies.
+        let _: mime::Mime = mime_guess_typed!("src/lib.rs");
+        let _: mime::Mime = mime_guess_typed!(default = "application/octet-stream", "LICENSE");
+        println!("{:?}", dir_mime_table!(recursive, "src"));
+        println!("{:?}", mime_map!("src"));
+        println!("{:?}", mime_map!(skip_unknown, "."));
+        println!("{:?}", mime_map!(default = "application/octet-stream", "."));
+        assert_eq!(content_extension!(default = "unknown", "Cargo.toml"), "unknown");
+        assert_eq!(
+            content_extension!("tests/fixtures/content/photo"),
+            path!("tests/fixtures/content/photo.png")
+        );
+        assert_eq!(
+            content_extension!("tests/fixtures/content/icon.png"),
+            path!("tests/fixtures/content/icon.png")
+        );
+        assert_eq!(
+            content_extension!("tests/fixtures/content/archive.tar"),
+            path!("tests/fixtures/content/archive.tar.gz")
+        );
+        assert_eq!(
+            content_extension!("tests/fixtures/content/document"),
+            path!("tests/fixtures/content/document.pdf")
+        );
+        assert_eq!(
+            content_extension!("tests/fixtures/content/module"),
+            path!("tests/fixtures/content/module.wasm")
+        );
+    }
+
+    #[cfg(feature = "jsonschema")]
+    {
+        println!(validate_schema!(
+            schema = "tests/fixtures/schema/config.schema.json",
+            "tests/fixtures/schema/valid.json"
+        ));
+    }
+
+    #[cfg(feature = "force-separator")]
+    {
+        println!(path_with_separator!(sep = '\\', "src/lib.rs"));
+        println!(path_with_separator!(sep = '/', "src", "lib.rs"));
+    }
+
+    #[cfg(feature = "hash")]
+    {
+        println!("{}", content_hash!("Cargo.toml"));
+        println!("{}", content_hash!(algo = "sha1", "Cargo.toml"));
+
+        const CONTENT_HASH: &str = content_hash!("Cargo.toml");
+        println!("{}", CONTENT_HASH);
+
+        println!("{}", tree_hash!("src"));
+        println!("{}", tree_hash!(algo = "sha1", "src"));
+        println!("{}", tree_hash!(max_depth = 1, "src"));
+
+        println!("{}", cachebust_url!("src/lib.rs"));
+        println!("{}", cachebust_url!(prefix = "/static", "src", "lib.rs"));
+
+        // The SHA-256 digest of `tests/fixtures/cachebust/app.css` starts with `535edfaf`.
+        assert_eq!(
+            cachebust_url!("tests/fixtures/cachebust/app.css"),
+            "tests/fixtures/cachebust/app.css?v=535edfaf"
+        );
+        assert_eq!(
+            cachebust_url!(prefix = "/static", "tests", "fixtures", "cachebust", "app.css"),
+            "/static/tests/fixtures/cachebust/app.css?v=535edfaf"
+        );
+    }
+
+    // With the `replace-separator` feature, only the separators of Windows paths are replaced, so the prefixes are kept.
+    #[cfg(all(windows, feature = "replace-separator"))]
+    {
+        assert_eq!(path!("C:/foo/bar"), r"C:\foo\bar");
+        assert_eq!(path!(r"\\server/share/foo"), r"\\server\share\foo");
+        assert_eq!(path_concat!("C:relative/path"), r"C:relative\path");
+        assert_eq!(path_concat!(r"C:\foo", r#"bar/baz"#), r"C:\foo\bar\baz");
+        assert_eq!(path_concat!(keep_separators, "C:/foo", "bar/baz"), r"C:/foo\bar/baz");
+    }
+
+    // Without the `replace-separator` feature, the input is kept as it is.
+    #[cfg(all(windows, not(feature = "replace-separator")))]
+    {
+        assert_eq!(path_concat!(r"C:\foo", r#"bar/baz"#), r"C:\foo\bar/baz");
+        assert_eq!(path_concat!(native_separators, r"C:\foo", "bar/baz"), r"C:\foo\bar\baz");
+    }
+
+    // The `ignore` feature lets `read_dir!` exclude the entries which are ignored by `.gitignore` files.
+    #[cfg(feature = "ignore")]
+    {
+        let entries: &[&str] = read_dir!(respect_gitignore, ".");
+        assert!(entries.contains(&path!("Cargo.toml")));
+        assert!(!entries.contains(&path!(".git")));
+        assert!(!entries.contains(&path!("target")));
+
+        // `.hidden` is hidden, and `backup.bak` and `build` are ignored by `tests/fixtures/ignore/.gitignore`.
+        assert_eq!(
+            read_dir!(respect_gitignore, "tests/fixtures/ignore"),
+            &[path!("tests/fixtures/ignore/kept.txt")]
+        );
+    }
+
+    #[cfg(feature = "glob")]
+    {
+        const RS_FILE_COUNT: usize = glob_count!("src/**/*.rs");
+        println!("{}", RS_FILE_COUNT);
+
+        let sources: &[&str] = glob_include_str!("src", "*.rs");
+        println!("{}", sources.len());
+    }
+
+    // The `home` feature expands a leading `~` to the home directory of the current user. `~user` is not supported.
+    #[cfg(feature = "home")]
+    {
+        println!(path!("~"));
+        println!(path!("~/.cargo"));
+        println!(path!("~", ".cargo", "bin"));
+    }
+
+    // The `workspace` feature enables `base = workspace`, which joins a relative path to the root directory of the workspace.
+    #[cfg(feature = "workspace")]
+    {
+        println!(path!(base = workspace, "Cargo.toml"));
     }
 
     // The `tuple` feature let these macros above support to input nested literal string tuples, which is useful when you want to use these macros inside a `macro_rule!` macro and concatenate with other literal strings.
@@ -43,5 +427,6 @@ fn main() {
         println!(path!(("foo", "bar"), "a"));
         println!(path!(("foo", "bar"), ("a", "b")));
         println!(path!(("foo", "bar", ("a", "b")), ("c", "d")));
+        println!(path!(("v", 2), ('a', "b")));
     }
 }
@@ -1,27 +1,59 @@
-#[cfg(all(windows, feature = "replace-separator"))]
+#[cfg(windows)]
 use std::ffi::OsString;
-use std::{ffi::OsStr, path::Path};
+use std::{
+    collections::HashMap,
+    ffi::OsStr,
+    fs::File,
+    io::{self, Read},
+    path::{Component, Path, PathBuf},
+    time::UNIX_EPOCH,
+};
 
-use quote::quote;
+use proc_macro2::{Group, Literal, Span, TokenTree};
+use quote::{quote, quote_spanned, ToTokens};
 use syn::Expr;
 
-use crate::TokenStream;
+#[cfg(feature = "hash")]
+use crate::join_builder::HashAlgorithm;
+use crate::{join_builder::DefaultValue, TokenStream};
 
-#[cfg(all(windows, feature = "replace-separator"))]
-// On Windows, `/` or `\` could be used as the path separator. We would prefer customarily using `/` as the separator in our hard code. This replacement is not necessary but can make the path look good.
+#[cfg(any(windows, feature = "force-separator"))]
+/// Replaces every `/` and `\` in a string with `to`, which must be `b'/'` or `b'\\'`.
+///
+/// Only the separators are replaced, so the colon of a drive prefix (`C:/foo`, `C:foo`) and the names of a UNC prefix (`\\server/share`) are kept. A verbatim path which starts with `\\?\` is returned as it is, because `/` is not a separator but a part of a name in such a path.
 #[inline]
-pub fn beautify_windows_path(mut s: String) -> String {
+pub fn replace_separator(mut s: String, to: u8) -> String {
+    debug_assert!(to == b'/' || to == b'\\');
+
+    if s.starts_with(r"\\?\") {
+        return s;
+    }
+
+    // Both the original bytes and `to` are ASCII, so the string is still UTF-8 after the replacement.
     let bytes = unsafe { s.as_mut_vec() };
 
     for b in bytes.iter_mut() {
-        if *b == b'/' {
-            *b = std::path::MAIN_SEPARATOR as u8;
+        if *b == b'/' || *b == b'\\' {
+            *b = to;
         }
     }
 
     s
 }
 
+#[cfg(windows)]
+// On Windows, `/` or `\` could be used as the path separator. We would prefer customarily using `/` as the separator in our hard code. This replacement is not necessary but can make the path look good.
+#[inline]
+pub fn beautify_windows_path(s: String) -> String {
+    replace_separator(s, std::path::MAIN_SEPARATOR as u8)
+}
+
+/// Replaces every `MAIN_SEPARATOR` in a string with `/`. It is the inverse of `beautify_windows_path`, which makes the path look the same on every platform when it is displayed.
+#[inline]
+pub fn slash_path(s: &str) -> String {
+    s.replace(std::path::MAIN_SEPARATOR, "/")
+}
+
 #[cfg(all(windows, feature = "replace-separator"))]
 #[inline]
 pub fn beautify_windows_path_os(s: OsString) -> Result<String, OsString> {
@@ -53,13 +85,7 @@ pub fn compile_error_not_directory<P: AsRef<Path>>(p: P) -> TokenStream {
 
 #[inline]
 pub fn compile_error_directory<P: AsRef<Path>>(p: P) -> TokenStream {
-    let p = p.as_ref();
-
-    if p.exists() {
-        compile_error(format!("The path {:?} is a directory", p))
-    } else {
-        compile_error_not_exist(p)
-    }
+    compile_error(format!("The path {:?} is a directory", p.as_ref()))
 }
 
 #[inline]
@@ -77,11 +103,673 @@ pub fn compile_error_not_absolute<P: AsRef<Path>>(p: P) -> TokenStream {
     compile_error(format!("The path {:?} is not absolute", p.as_ref()))
 }
 
+/// Emits the custom message if it is given, with `{}` replaced by the path. Otherwise, emits the error built by `default`. The error points at `span`.
+#[inline]
+pub fn compile_error_with_message<P: AsRef<Path>>(
+    msg: Option<&str>,
+    span: Span,
+    p: P,
+    default: impl FnOnce(P) -> TokenStream,
+) -> TokenStream {
+    let error = match msg {
+        Some(msg) => compile_error(msg.replace("{}", &p.as_ref().display().to_string())),
+        None => default(p),
+    };
+
+    respan(error, span)
+}
+
+/// Outputs the default value if it is given. Otherwise, emits the error like `compile_error_with_message`.
+#[inline]
+pub fn output_default_or_error<P: AsRef<Path>>(
+    default_value: Option<&DefaultValue>,
+    msg: Option<&str>,
+    span: Span,
+    p: P,
+    error: impl FnOnce(P) -> TokenStream,
+) -> TokenStream {
+    match default_value {
+        Some(default_value) => output_default_path(default_value),
+        None => compile_error_with_message(msg, span, p, error),
+    }
+}
+
+/// Sets the span of every token, so that an error emitted by the tokens points at the span.
+pub fn respan(tokens: TokenStream, span: Span) -> TokenStream {
+    fn respan_tokens(tokens: proc_macro2::TokenStream, span: Span) -> proc_macro2::TokenStream {
+        tokens
+            .into_iter()
+            .map(|mut token| {
+                if let TokenTree::Group(group) = &token {
+                    let mut new_group =
+                        Group::new(group.delimiter(), respan_tokens(group.stream(), span));
+
+                    new_group.set_span(span);
+
+                    token = TokenTree::Group(new_group);
+                } else {
+                    token.set_span(span);
+                }
+
+                token
+            })
+            .collect()
+    }
+
+    respan_tokens(tokens.into(), span).into()
+}
+
+#[inline]
+pub fn compile_error_io<P: AsRef<Path>>(p: P, err: io::Error) -> TokenStream {
+    compile_error(format!("The path {:?} cannot be read: {}", p.as_ref(), err))
+}
+
+#[inline]
+pub fn path_to_utf8_str(p: &Path) -> Result<&str, TokenStream> {
+    match p.to_str() {
+        Some(utf8_str) if utf8_str.contains('\0') => {
+            Err(compile_error(format!("The path {:?} contains a NUL byte", p)))
+        },
+        Some(utf8_str) => Ok(utf8_str),
+        None => Err(compile_error(format!(
+            "The path {:?} cannot be canonicalized to a UTF-8 string.",
+            p
+        ))),
+    }
+}
+
+/// Joins the components of a relative path with `/`, regardless of the platform.
+pub fn relative_path_to_slash_string(p: &Path) -> Result<String, TokenStream> {
+    let mut s = String::new();
+
+    for component in p.components() {
+        let component = path_to_utf8_str(component.as_ref())?;
+
+        if !s.is_empty() {
+            s.push('/');
+        }
+
+        s.push_str(component);
+    }
+
+    Ok(s)
+}
+
+/// Encodes bytes to URL-safe base64 without padding.
+pub fn encode_base64_url(bytes: &[u8]) -> String {
+    const TABLE: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+    let mut s = String::with_capacity((bytes.len() * 4 + 2) / 3);
+
+    for chunk in bytes.chunks(3) {
+        let n = chunk.iter().enumerate().fold(0u32, |n, (i, b)| n | (*b as u32) << (16 - i * 8));
+
+        for i in 0..=chunk.len() {
+            s.push(TABLE[(n >> (18 - i * 6)) as usize & 0x3F] as char);
+        }
+    }
+
+    s
+}
+
+/// Joins a URL prefix and a relative path with `/`. An empty prefix is ignored, and an empty path results in the prefix itself.
+pub fn join_url_prefix(prefix: &str, path: &str) -> String {
+    if prefix.is_empty() {
+        path.to_string()
+    } else if path.is_empty() {
+        prefix.to_string()
+    } else {
+        format!("{}/{}", prefix.trim_end_matches('/'), path)
+    }
+}
+
+/// Collects the files in a directory, sorted by their paths. The directory itself is at depth `0`, the files directly in it are at depth `1`, and the files in its subdirectories are at depth `2` and so on. Files deeper than `max_depth` are not collected. Symbolic links to directories are not followed, so there is no risk of looping forever.
+pub fn read_dir_files<P: AsRef<Path>>(p: P, max_depth: Option<usize>) -> io::Result<Vec<PathBuf>> {
+    fn collect(
+        p: &Path,
+        depth: usize,
+        max_depth: Option<usize>,
+        files: &mut Vec<PathBuf>,
+    ) -> io::Result<()> {
+        if max_depth.map(|max_depth| depth > max_depth).unwrap_or(false) {
+            return Ok(());
+        }
+
+        for entry in p.read_dir()? {
+            let entry = entry?;
+
+            let path = entry.path();
+
+            if entry.file_type()?.is_dir() {
+                collect(&path, depth + 1, max_depth, files)?;
+            } else if path.is_file() {
+                files.push(path);
+            }
+        }
+
+        Ok(())
+    }
+
+    let mut files = Vec::new();
+
+    collect(p.as_ref(), 1, max_depth, &mut files)?;
+
+    files.sort();
+
+    Ok(files)
+}
+
+/// Finds two entries in the same directory whose names differ only in case, which would collide on a case-insensitive file system. The entries directly in the directory are at depth `1`, and the entries deeper than `max_depth` are not checked. Symbolic links to directories are not followed.
+pub fn find_case_collision<P: AsRef<Path>>(
+    p: P,
+    max_depth: Option<usize>,
+) -> io::Result<Option<(PathBuf, PathBuf)>> {
+    if max_depth == Some(0) {
+        return Ok(None);
+    }
+
+    let mut entries = p.as_ref().read_dir()?.collect::<io::Result<Vec<_>>>()?;
+
+    entries.sort_by_key(|entry| entry.file_name());
+
+    let mut names: HashMap<String, PathBuf> = HashMap::with_capacity(entries.len());
+
+    for entry in entries.iter() {
+        let path = entry.path();
+
+        if let Some(name) = entry.file_name().to_str() {
+            if let Some(other_path) = names.insert(name.to_lowercase(), path.clone()) {
+                return Ok(Some((other_path, path)));
+            }
+        }
+    }
+
+    for entry in entries {
+        if entry.file_type()?.is_dir() {
+            if let Some(collision) =
+                find_case_collision(entry.path(), max_depth.map(|max_depth| max_depth - 1))?
+            {
+                return Ok(Some(collision));
+            }
+        }
+    }
+
+    Ok(None)
+}
+
+#[cfg(feature = "ignore")]
+/// Collects the entries in a directory which are neither hidden nor ignored by the `.gitignore`, `.ignore` and `.git/info/exclude` files in the directory, its subdirectories and its ancestors, sorted by their paths. Entries deeper than `max_depth` are not collected. The directory itself is not included.
+///
+/// The global gitignore file of the user is not respected, so that the result does not depend on the machine.
+pub fn read_dir_not_ignored<P: AsRef<Path>>(
+    p: P,
+    max_depth: Option<usize>,
+) -> Result<Vec<PathBuf>, ignore::Error> {
+    // `components` drops the `.` components, so that they are not in the output paths.
+    let p: PathBuf = p.as_ref().components().collect();
+
+    let mut entries = Vec::new();
+
+    for entry in ignore::WalkBuilder::new(p)
+        .hidden(true)
+        .ignore(true)
+        .git_ignore(true)
+        .git_exclude(true)
+        .git_global(false)
+        .parents(true)
+        .require_git(false)
+        .max_depth(max_depth)
+        .build()
+    {
+        let entry = entry?;
+
+        if entry.depth() > 0 {
+            entries.push(entry.into_path());
+        }
+    }
+
+    entries.sort();
+
+    Ok(entries)
+}
+
+#[cfg(feature = "workspace")]
+/// Finds the root directory of the workspace which the package belongs to in the same way as Cargo. If the manifest of the package sets `package.workspace`, the root is that directory, normalized lexically. Otherwise, it is the nearest directory containing a `Cargo.toml` file with a `workspace` table, searched from the manifest directory up to the root. If there is no such directory, the package is the root of its own workspace.
+///
+/// Like Cargo, a manifest which only has `[workspace.dependencies]` has a `workspace` table. A manifest which cannot be read or parsed is skipped.
+pub fn find_workspace_root(manifest_dir: &Path) -> PathBuf {
+    #[inline]
+    fn read_manifest(dir: &Path) -> Option<toml::Table> {
+        std::fs::read_to_string(dir.join("Cargo.toml")).ok()?.parse().ok()
+    }
+
+    let explicit_root = read_manifest(manifest_dir).and_then(|manifest| {
+        manifest
+            .get("package")?
+            .get("workspace")?
+            .as_str()
+            .map(|root| normalize_path(&manifest_dir.join(root)))
+    });
+
+    if let Some(root) = explicit_root {
+        return root;
+    }
+
+    for dir in manifest_dir.ancestors() {
+        if let Some(manifest) = read_manifest(dir) {
+            if manifest.contains_key("workspace") {
+                return dir.to_path_buf();
+            }
+        }
+    }
+
+    manifest_dir.to_path_buf()
+}
+
+#[cfg(feature = "glob")]
+/// Collects the paths which match a glob pattern, sorted by their paths. A relative pattern is relative to the CARGO_MANIFEST_DIR, whose special characters are escaped.
+///
+/// If `respect_gitignore` is `true`, the paths ignored by the `.gitignore` files are excluded. The ignore files are searched from the longest leading part of the pattern which contains no special characters.
+pub fn glob_paths(pattern: &Path, _respect_gitignore: bool) -> Result<Vec<PathBuf>, String> {
+    let pattern_str = path_to_utf8_str(pattern)
+        .map_err(|_| format!("The pattern {:?} is not a UTF-8 string", pattern))?;
+
+    let pattern_str = if pattern.is_absolute() {
+        pattern_str.to_string()
+    } else {
+        let manifest_dir = path_to_utf8_str(&crate::MANIFEST_DIR).map_err(|_| {
+            format!("The CARGO_MANIFEST_DIR {:?} is not a UTF-8 string", *crate::MANIFEST_DIR)
+        })?;
+
+        Path::new(&glob::Pattern::escape(manifest_dir))
+            .join(pattern_str)
+            .to_string_lossy()
+            .into_owned()
+    };
+
+    let paths = glob::glob(&pattern_str)
+        .map_err(|err| format!("The pattern {:?} is invalid: {}", pattern, err))?;
+
+    let mut matched_paths = Vec::new();
+
+    for p in paths {
+        matched_paths.push(p.map_err(|err| err.to_string())?);
+    }
+
+    #[cfg(feature = "ignore")]
+    if _respect_gitignore {
+        let p = if pattern.is_absolute() {
+            pattern.to_path_buf()
+        } else {
+            crate::MANIFEST_DIR.join(pattern)
+        };
+
+        let root = p
+            .ancestors()
+            .find(|dir| {
+                dir.to_str().map(|s| !s.contains(['*', '?', '[']) && dir.is_dir()).unwrap_or(false)
+            })
+            .unwrap_or(&p);
+
+        let not_ignored = read_dir_not_ignored(root, None)
+            .map_err(|err| format!("The path {:?} cannot be read: {}", root, err))?;
+
+        matched_paths.retain(|p| not_ignored.binary_search(p).is_ok());
+    }
+
+    matched_paths.sort();
+
+    Ok(matched_paths)
+}
+
+/// Gets the last modification time of a file, in seconds since the UNIX epoch.
+pub fn modified_secs<P: AsRef<Path>>(p: P) -> io::Result<u64> {
+    let modified = p.as_ref().metadata()?.modified()?;
+
+    match modified.duration_since(UNIX_EPOCH) {
+        Ok(duration) => Ok(duration.as_secs()),
+        Err(_) => Err(io::Error::new(io::ErrorKind::Other, "the time is before the UNIX epoch")),
+    }
+}
+
+#[cfg(feature = "jsonschema")]
+/// Reads and parses a JSON file.
+pub fn read_json_file<P: AsRef<Path>>(p: P) -> Result<serde_json::Value, TokenStream> {
+    let p = p.as_ref();
+
+    if !p.is_file() {
+        return Err(compile_error_not_file(p));
+    }
+
+    let data = std::fs::read(p).map_err(|err| compile_error_io(p, err))?;
+
+    serde_json::from_slice(&data)
+        .map_err(|err| compile_error(format!("The file {:?} is not valid JSON: {}", p, err)))
+}
+
+#[cfg(feature = "mime_guess")]
+/// Detects the type of a file by its magic number and returns the customary file extension of that type.
+pub fn detect_extension_by_magic<P: AsRef<Path>>(p: P) -> io::Result<Option<&'static str>> {
+    let mut bytes = Vec::with_capacity(16);
+
+    File::open(p)?.take(16).read_to_end(&mut bytes)?;
+
+    let extension = match bytes.as_slice() {
+        [0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A, ..] => "png",
+        [0xFF, 0xD8, 0xFF, ..] => "jpg",
+        [b'G', b'I', b'F', b'8', b'7' | b'9', b'a', ..] => "gif",
+        [b'R', b'I', b'F', b'F', _, _, _, _, b'W', b'E', b'B', b'P', ..] => "webp",
+        [b'R', b'I', b'F', b'F', _, _, _, _, b'W', b'A', b'V', b'E', ..] => "wav",
+        [b'I', b'I', 0x2A, 0x00, ..] | [b'M', b'M', 0x00, 0x2A, ..] => "tiff",
+        [0x00, 0x00, 0x01, 0x00, ..] => "ico",
+        [b'B', b'M', ..] => "bmp",
+        [b'%', b'P', b'D', b'F', b'-', ..] => "pdf",
+        [b'P', b'K', 0x03, 0x04, ..] => "zip",
+        [0x1F, 0x8B, ..] => "gz",
+        [b'B', b'Z', b'h', ..] => "bz2",
+        [0xFD, b'7', b'z', b'X', b'Z', 0x00, ..] => "xz",
+        [0x28, 0xB5, 0x2F, 0xFD, ..] => "zst",
+        [b'7', b'z', 0xBC, 0xAF, 0x27, 0x1C, ..] => "7z",
+        [0x00, b'a', b's', b'm', ..] => "wasm",
+        [b'w', b'O', b'F', b'F', ..] => "woff",
+        [b'w', b'O', b'F', b'2', ..] => "woff2",
+        [b'O', b'g', b'g', b'S', ..] => "ogg",
+        [b'f', b'L', b'a', b'C', ..] => "flac",
+        [b'I', b'D', b'3', ..] => "mp3",
+        _ => return Ok(None),
+    };
+
+    Ok(Some(extension))
+}
+
+#[cfg(feature = "hash")]
+#[inline]
+pub fn to_hex_string(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[cfg(feature = "hash")]
+/// Computes the digest of data and returns it as a lowercase hex string.
+pub fn hash_bytes(data: &[u8], algorithm: HashAlgorithm) -> String {
+    use sha1::Digest;
+
+    match algorithm {
+        HashAlgorithm::Sha256 => to_hex_string(&sha2::Sha256::digest(data)),
+        HashAlgorithm::Sha1 => to_hex_string(&sha1::Sha1::digest(data)),
+    }
+}
+
+#[cfg(feature = "hash")]
+/// Computes the digest of the files in a directory. For each file in order, the path relative to the directory joined with `/`, a NUL byte, the length of the contents as a 64-bit little-endian integer and the contents are hashed, so the digest is the same on every platform.
+pub fn hash_tree(
+    dir: &Path,
+    files: &[PathBuf],
+    algorithm: HashAlgorithm,
+) -> Result<String, TokenStream> {
+    use sha1::Digest;
+
+    fn digest<D: Digest>(dir: &Path, files: &[PathBuf]) -> Result<String, TokenStream> {
+        let mut hasher = D::new();
+
+        for file in files {
+            let name = relative_path_to_slash_string(file.strip_prefix(dir).unwrap_or(file))?;
+
+            let data = std::fs::read(file).map_err(|err| compile_error_io(file, err))?;
+
+            hasher.update(name.as_bytes());
+            hasher.update([0]);
+            hasher.update((data.len() as u64).to_le_bytes());
+            hasher.update(&data);
+        }
+
+        Ok(to_hex_string(&hasher.finalize()))
+    }
+
+    match algorithm {
+        HashAlgorithm::Sha256 => digest::<sha2::Sha256>(dir, files),
+        HashAlgorithm::Sha1 => digest::<sha1::Sha1>(dir, files),
+    }
+}
+
+/// Detects the byte-order mark at the beginning of a file. Returns `"utf8"`, `"utf16le"`, `"utf16be"` or `"none"`.
+pub fn detect_bom<P: AsRef<Path>>(p: P) -> io::Result<&'static str> {
+    let mut bytes = Vec::with_capacity(3);
+
+    File::open(p)?.take(3).read_to_end(&mut bytes)?;
+
+    let kind = match bytes.as_slice() {
+        [0xEF, 0xBB, 0xBF] => "utf8",
+        [0xFF, 0xFE, ..] => "utf16le",
+        [0xFE, 0xFF, ..] => "utf16be",
+        _ => "none",
+    };
+
+    Ok(kind)
+}
+
+/// Returns the lowercased file extension of the path if it is in the denylist, which should be lowercased too.
+#[inline]
+pub fn find_denied_extension(p: &Path, denylist: &[String]) -> Option<String> {
+    let extension = p.extension()?.to_string_lossy().to_lowercase();
+
+    if denylist.contains(&extension) {
+        Some(extension)
+    } else {
+        None
+    }
+}
+
+/// Normalizes the path lexically by removing `.` components and collapsing `..` components with their preceding names. The file system is not accessed, so symbolic links are not resolved. The `..` components right after the root are dropped.
+pub fn normalize_path(p: &Path) -> PathBuf {
+    let mut normalized_path = PathBuf::new();
+
+    for component in p.components() {
+        match component {
+            Component::CurDir => (),
+            Component::ParentDir => match normalized_path.components().next_back() {
+                Some(Component::Normal(_)) => {
+                    normalized_path.pop();
+                },
+                Some(Component::Prefix(_)) | Some(Component::RootDir) => (),
+                _ => normalized_path.push(component),
+            },
+            _ => normalized_path.push(component),
+        }
+    }
+
+    normalized_path
+}
+
+/// Computes the relative path from the directory `base` to the path `p` lexically. Both of them should be normalized absolute paths.
+pub fn relative_path_between(base: &Path, p: &Path) -> PathBuf {
+    let mut base_components = base.components().peekable();
+    let mut components = p.components().peekable();
+
+    while let (Some(a), Some(b)) = (base_components.peek(), components.peek()) {
+        if a != b {
+            break;
+        }
+
+        base_components.next();
+        components.next();
+    }
+
+    let mut relative_path = PathBuf::new();
+
+    for _ in base_components {
+        relative_path.push(Component::ParentDir);
+    }
+
+    for component in components {
+        relative_path.push(component);
+    }
+
+    if relative_path.as_os_str().is_empty() {
+        relative_path.push(Component::CurDir);
+    }
+
+    relative_path
+}
+
+#[cfg(windows)]
+/// Converts an absolute path to the extended-length form prefixed with `\\?\`. The `.` and `..` components are resolved lexically because they are not allowed in such a path.
+pub fn to_verbatim_path(p: &Path) -> PathBuf {
+    use std::path::Prefix;
+
+    let mut components = p.components();
+
+    let mut s = match components.next() {
+        Some(Component::Prefix(prefix)) => match prefix.kind() {
+            Prefix::UNC(server, share) => {
+                let mut s = OsString::from(r"\\?\UNC\");
+
+                s.push(server);
+                s.push(r"\");
+                s.push(share);
+
+                s
+            },
+            Prefix::Disk(disk) => OsString::from(format!(r"\\?\{}:", disk as char)),
+            // already verbatim, or a device path which cannot be converted
+            _ => return p.to_path_buf(),
+        },
+        _ => return p.to_path_buf(),
+    };
+
+    let mut names: Vec<&OsStr> = Vec::new();
+
+    for component in components {
+        match component {
+            Component::Normal(name) => names.push(name),
+            Component::ParentDir => {
+                names.pop();
+            },
+            _ => (),
+        }
+    }
+
+    if names.is_empty() {
+        s.push(r"\");
+    }
+
+    for name in names {
+        s.push(r"\");
+        s.push(name);
+    }
+
+    PathBuf::from(s)
+}
+
+#[cfg(windows)]
+/// Gets the short (8.3) form of an existing path by `GetShortPathNameW`.
+pub fn get_short_path(p: &Path) -> io::Result<PathBuf> {
+    use std::os::windows::ffi::{OsStrExt, OsStringExt};
+
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn GetShortPathNameW(
+            long_path: *const u16,
+            short_path: *mut u16,
+            buffer_length: u32,
+        ) -> u32;
+    }
+
+    let long_path: Vec<u16> = p.as_os_str().encode_wide().chain(Some(0)).collect();
+
+    let mut buffer: Vec<u16> = vec![0; 260];
+
+    loop {
+        // the returned length excludes the terminating null character on success, and includes it if the buffer is too small
+        let length = unsafe {
+            GetShortPathNameW(long_path.as_ptr(), buffer.as_mut_ptr(), buffer.len() as u32)
+        } as usize;
+
+        if length == 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        if length < buffer.len() {
+            buffer.truncate(length);
+
+            return Ok(PathBuf::from(OsString::from_wide(&buffer)));
+        }
+
+        buffer.resize(length, 0);
+    }
+}
+
+#[cfg(windows)]
+/// Checks whether the names of all the components of the path fit in the 8.3 format, so that they do not need short names.
+pub fn is_8dot3_path(p: &Path) -> bool {
+    p.components().all(|component| match component {
+        Component::Normal(name) => match name.to_str() {
+            Some(name) if name.is_ascii() && !name.contains(' ') => {
+                let mut parts = name.splitn(2, '.');
+
+                let stem = parts.next().unwrap_or("");
+                let extension = parts.next().unwrap_or("");
+
+                !stem.is_empty()
+                    && stem.len() <= 8
+                    && extension.len() <= 3
+                    && !extension.contains('.')
+            },
+            _ => false,
+        },
+        _ => true,
+    })
+}
+
+#[inline]
+pub fn eq_ignore_case(a: &OsStr, b: &OsStr) -> bool {
+    match (a.to_str(), b.to_str()) {
+        (Some(a), Some(b)) => a.to_lowercase() == b.to_lowercase(),
+        _ => a == b,
+    }
+}
+
+#[cfg(any(feature = "case-insensitive", feature = "canonical-case"))]
+/// Finds the casing of a path which is actually used on the disk by comparing the names of directory entries case-insensitively. An entry whose name matches exactly is preferred. Returns `None` if any component of the path cannot be found.
+pub fn find_path_case_insensitively<P: AsRef<Path>>(p: P) -> Option<PathBuf> {
+    let mut result = PathBuf::new();
+
+    for component in p.as_ref().components() {
+        match component {
+            Component::Normal(name) => {
+                let dir = if result.as_os_str().is_empty() { Path::new(".") } else { &result };
+
+                let mut matched = None;
+
+                for entry in dir.read_dir().ok()? {
+                    let entry_name = entry.ok()?.file_name();
+
+                    if entry_name == name {
+                        matched = Some(entry_name);
+                        break;
+                    }
+
+                    if matched.is_none() && eq_ignore_case(&entry_name, name) {
+                        matched = Some(entry_name);
+                    }
+                }
+
+                result.push(matched?);
+            },
+            _ => result.push(component),
+        }
+    }
+
+    Some(result)
+}
+
 #[inline]
 pub fn output_os_str<S: AsRef<OsStr>>(s: S) -> TokenStream {
     let s = s.as_ref();
 
     match s.to_str() {
+        Some(utf8_str) if utf8_str.contains('\0') => {
+            compile_error(format!("The OsStr {:?} contains a NUL byte", s))
+        },
         Some(utf8_str) => {
             let code = quote! {
                 #utf8_str
@@ -95,24 +783,309 @@ pub fn output_os_str<S: AsRef<OsStr>>(s: S) -> TokenStream {
     }
 }
 
+/// Outputs the string as a `&OsStr` expression. On Unix, a string which cannot be represented in UTF-8 is output by its bytes.
 #[inline]
-pub fn output_path<P: AsRef<Path>>(p: P) -> TokenStream {
-    let p = p.as_ref();
+pub fn output_os_str_typed<S: AsRef<OsStr>>(s: S) -> TokenStream {
+    let s = s.as_ref();
 
-    match p.to_str() {
+    match s.to_str() {
         Some(utf8_str) => {
+            let code = quote! {
+                ::std::ffi::OsStr::new(#utf8_str)
+            };
+
+            code.into()
+        },
+        None => {
+            #[cfg(unix)]
+            {
+                use std::os::unix::ffi::OsStrExt;
+
+                let bytes = Literal::byte_string(s.as_bytes());
+
+                let code = quote! {
+                    <::std::ffi::OsStr as ::std::os::unix::ffi::OsStrExt>::from_bytes(#bytes)
+                };
+
+                code.into()
+            }
+
+            #[cfg(not(unix))]
+            {
+                compile_error(format!(
+                    "The OsStr {:?} cannot be canonicalized to a UTF-8 string.",
+                    s
+                ))
+            }
+        },
+    }
+}
+
+/// Outputs a `usize` literal with the suffix, e.g. `3usize`, so that it can be used in `const` items without ambiguity.
+#[inline]
+pub fn output_usize(n: usize) -> TokenStream {
+    let lit = Literal::usize_suffixed(n);
+
+    let code = quote! {
+        #lit
+    };
+
+    code.into()
+}
+
+/// Outputs an `isize` literal with the suffix, e.g. `-3isize`.
+pub fn output_isize(n: isize) -> TokenStream {
+    let lit = Literal::isize_suffixed(n);
+
+    let code = quote! {
+        #lit
+    };
+
+    code.into()
+}
+
+/// Outputs a `u64` literal with the suffix, e.g. `3u64`, so that it can be used in `const` items without ambiguity.
+#[inline]
+pub fn output_u64(n: u64) -> TokenStream {
+    let lit = Literal::u64_suffixed(n);
+
+    let code = quote! {
+        #lit
+    };
+
+    code.into()
+}
+
+#[inline]
+pub fn output_path<P: AsRef<Path>>(p: P) -> TokenStream {
+    match path_to_utf8_str(p.as_ref()) {
+        Ok(utf8_str) => {
             let code = quote! {
                 #utf8_str
             };
 
             code.into()
         },
+        Err(err) => err,
+    }
+}
+
+/// Outputs a value along with an `include_bytes!` on the file, so that the compiler rebuilds the code when the file changes. The output is a block expression rather than a literal.
+#[inline]
+pub fn output_tracked<T: ToTokens>(p: &Path, value: T) -> TokenStream {
+    match path_to_utf8_str(p) {
+        Ok(p) => {
+            let code = quote! {
+                {
+                    const _: &[u8] = include_bytes!(#p);
+
+                    #value
+                }
+            };
+
+            code.into()
+        },
+        Err(err) => err,
+    }
+}
+
+/// Outputs the paths as a `&[&str]` slice.
+#[inline]
+pub fn output_path_slice<P: AsRef<Path>>(paths: &[P]) -> TokenStream {
+    let mut utf8_strs = Vec::with_capacity(paths.len());
+
+    for p in paths {
+        match path_to_utf8_str(p.as_ref()) {
+            Ok(utf8_str) => utf8_strs.push(utf8_str),
+            Err(err) => return err,
+        }
+    }
+
+    let code = quote! {
+        &[#(#utf8_strs),*] as &[&str]
+    };
+
+    code.into()
+}
+
+/// Outputs the path as a `&'static Path` expression by `Path::new`.
+#[inline]
+pub fn output_static_path<P: AsRef<Path>>(p: P) -> TokenStream {
+    match path_to_utf8_str(p.as_ref()) {
+        Ok(utf8_str) => {
+            let code = quote! {
+                ::std::path::Path::new(#utf8_str)
+            };
+
+            code.into()
+        },
+        Err(err) => err,
+    }
+}
+
+#[cfg(feature = "mime_guess")]
+/// Guesses the mime types of the path. Some well-known file names which have no extensions, such as `Makefile` and `.gitignore`, are looked up by the whole file name before the extension is used.
+pub fn guess_mimes(p: &Path) -> Vec<mime_guess::Mime> {
+    const FILE_NAME_MIMES: [(&str, &str); 19] = [
+        ("Makefile", "text/x-makefile"),
+        ("makefile", "text/x-makefile"),
+        ("GNUmakefile", "text/x-makefile"),
+        ("Dockerfile", "text/x-dockerfile"),
+        ("Containerfile", "text/x-dockerfile"),
+        ("Gemfile", "text/x-ruby"),
+        ("Rakefile", "text/x-ruby"),
+        ("Vagrantfile", "text/x-ruby"),
+        ("Jenkinsfile", "text/x-groovy"),
+        ("Procfile", "text/plain"),
+        (".gitignore", "text/plain"),
+        (".gitattributes", "text/plain"),
+        (".gitmodules", "text/plain"),
+        (".dockerignore", "text/plain"),
+        (".npmignore", "text/plain"),
+        (".editorconfig", "text/plain"),
+        (".bashrc", "application/x-sh"),
+        (".bash_profile", "application/x-sh"),
+        (".zshrc", "application/x-sh"),
+    ];
+
+    let file_name = p.file_name().and_then(|file_name| file_name.to_str());
+
+    match FILE_NAME_MIMES.iter().find(|(name, _)| Some(*name) == file_name) {
+        Some((_, mime)) => vec![mime.parse().unwrap()],
+        None => mime_guess::from_path(p).iter().collect(),
+    }
+}
+
+#[cfg(feature = "mime_guess")]
+/// Gets the name of the constant in the `mime` crate which equals the mime type.
+pub fn mime_constant_name(mime: &mime_guess::Mime) -> Option<&'static str> {
+    use mime_guess::mime;
+
+    let constants = [
+        (mime::TEXT_PLAIN, "TEXT_PLAIN"),
+        (mime::TEXT_HTML, "TEXT_HTML"),
+        (mime::TEXT_CSS, "TEXT_CSS"),
+        (mime::TEXT_JAVASCRIPT, "TEXT_JAVASCRIPT"),
+        (mime::TEXT_XML, "TEXT_XML"),
+        (mime::TEXT_EVENT_STREAM, "TEXT_EVENT_STREAM"),
+        (mime::TEXT_CSV, "TEXT_CSV"),
+        (mime::TEXT_TAB_SEPARATED_VALUES, "TEXT_TAB_SEPARATED_VALUES"),
+        (mime::TEXT_VCARD, "TEXT_VCARD"),
+        (mime::IMAGE_JPEG, "IMAGE_JPEG"),
+        (mime::IMAGE_GIF, "IMAGE_GIF"),
+        (mime::IMAGE_PNG, "IMAGE_PNG"),
+        (mime::IMAGE_BMP, "IMAGE_BMP"),
+        (mime::IMAGE_SVG, "IMAGE_SVG"),
+        (mime::FONT_WOFF, "FONT_WOFF"),
+        (mime::FONT_WOFF2, "FONT_WOFF2"),
+        (mime::APPLICATION_JSON, "APPLICATION_JSON"),
+        (mime::APPLICATION_JAVASCRIPT, "APPLICATION_JAVASCRIPT"),
+        (mime::APPLICATION_WWW_FORM_URLENCODED, "APPLICATION_WWW_FORM_URLENCODED"),
+        (mime::APPLICATION_OCTET_STREAM, "APPLICATION_OCTET_STREAM"),
+        (mime::APPLICATION_MSGPACK, "APPLICATION_MSGPACK"),
+        (mime::APPLICATION_PDF, "APPLICATION_PDF"),
+        (mime::MULTIPART_FORM_DATA, "MULTIPART_FORM_DATA"),
+    ];
+
+    constants.iter().find(|(constant, _)| constant == mime).map(|(_, name)| *name)
+}
+
+#[cfg(feature = "mime_guess")]
+/// Outputs a `mime::Mime` expression. A constant of the `mime` crate is used if there is one which equals the mime type. Otherwise, the mime type is parsed at runtime.
+pub fn output_mime(mime: &mime_guess::Mime) -> TokenStream {
+    let code = match mime_constant_name(mime) {
+        Some(name) => {
+            let ident = proc_macro2::Ident::new(name, Span::call_site());
+
+            quote! {
+                ::mime::#ident
+            }
+        },
         None => {
-            compile_error(format!("The path {:?} cannot be canonicalized to a UTF-8 string.", p))
+            let s = mime.to_string();
+
+            quote! {
+                #s.parse::<::mime::Mime>().unwrap()
+            }
+        },
+    };
+
+    code.into()
+}
+
+/// Outputs the absolute path `p`. If a URL prefix is given, outputs the relative path joined to the prefix with `/` instead, regardless of the platform and the separator features. The relative path which is normalized lexically must not leave the CARGO_MANIFEST_DIR.
+pub fn output_path_with_prefix(
+    prefix: Option<&str>,
+    relative_path: &Path,
+    p: &Path,
+) -> TokenStream {
+    match prefix {
+        Some(prefix) => {
+            let normalized_path = normalize_path(relative_path);
+
+            if normalized_path.starts_with(Component::ParentDir) {
+                return compile_error(format!(
+                    "The path {:?} is not in the CARGO_MANIFEST_DIR, so it cannot be joined to \
+                     the URL prefix {:?}",
+                    relative_path, prefix
+                ));
+            }
+
+            match relative_path_to_slash_string(&normalized_path) {
+                Ok(s) => {
+                    let s = join_url_prefix(prefix, &s);
+
+                    let code = quote! {
+                        #s
+                    };
+
+                    code.into()
+                },
+                Err(err) => err,
+            }
+        },
+        None => output_path(p),
+    }
+}
+
+/// Outputs the path along with a warning. Custom warnings are not supported on stable Rust, so the warning is emitted by calling a deprecated function whose note is the message. The output is a block expression rather than a literal.
+pub fn output_path_with_warning<P: AsRef<Path>>(p: P, warning: &str, span: Span) -> TokenStream {
+    match path_to_utf8_str(p.as_ref()) {
+        Ok(utf8_str) => {
+            let call = quote_spanned! {span=>
+                manifest_dir_macros_warning();
+            };
+
+            let code = quote! {
+                {
+                    #[deprecated(note = #warning)]
+                    #[inline]
+                    const fn manifest_dir_macros_warning() {}
+
+                    #call
+
+                    #utf8_str
+                }
+            };
+
+            code.into()
         },
+        Err(err) => err,
     }
 }
 
+/// Wraps the output of another macro, which is a `&str` expression or a compile error, by `String::from`.
+#[inline]
+pub fn output_string(tokens: TokenStream) -> TokenStream {
+    let tokens = proc_macro2::TokenStream::from(tokens);
+
+    let code = quote! {
+        ::std::string::String::from(#tokens)
+    };
+
+    code.into()
+}
+
 #[inline]
 pub fn output_expr(expr: &Expr) -> TokenStream {
     let code = quote! {
@@ -121,3 +1094,19 @@ pub fn output_expr(expr: &Expr) -> TokenStream {
 
     code.into()
 }
+
+#[inline]
+pub fn output_default_os_str(default_value: &DefaultValue) -> TokenStream {
+    match default_value {
+        DefaultValue::LitStr(s) => output_os_str(s),
+        DefaultValue::Expr(expr) => output_expr(expr),
+    }
+}
+
+#[inline]
+pub fn output_default_path(default_value: &DefaultValue) -> TokenStream {
+    match default_value {
+        DefaultValue::LitStr(s) => output_path(s),
+        DefaultValue::Expr(expr) => output_expr(expr),
+    }
+}
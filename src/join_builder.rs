@@ -1,98 +1,655 @@
-use std::path::PathBuf;
+use std::{
+    env,
+    path::{self, Path, PathBuf},
+};
 
-#[cfg(feature = "tuple")]
+use proc_macro2::{Span, TokenStream};
 use quote::ToTokens;
 #[cfg(feature = "tuple")]
 use syn::spanned::Spanned;
-#[cfg(feature = "tuple")]
-use syn::Lit;
 use syn::{
-    parse::{Parse, ParseStream},
-    Expr, LitStr, Token,
+    parse::{Parse, ParseStream, Parser},
+    punctuated::Punctuated,
+    Expr, Lit, LitByteStr, LitInt, LitStr, Token,
 };
 
-pub struct JoinBuilder(pub PathBuf);
+/// The second field indicates whether the input path ends with a path separator.
+pub struct JoinBuilder(pub PathBuf, pub bool);
 pub struct JoinBuilderNoBeautify(pub PathBuf);
+/// The second field is the `msg =` option.
+pub struct JoinBuilderWithMessage(pub PathBuf, pub Option<String>, pub ComponentSpans);
 
-pub struct JoinBuilderWithDefaultValue(pub PathBuf, pub Option<Expr>);
-pub struct JoinBuilderNoBeautifyWithDefaultValue(pub PathBuf, pub Option<Expr>);
+/// The second field is the `msg =` option, and the third field is the `prefix =` option.
+pub struct JoinBuilderWithMessageAndPrefix(
+    pub PathBuf,
+    pub Option<String>,
+    pub Option<String>,
+    pub ComponentSpans,
+);
 
-#[cfg(not(feature = "tuple"))]
-fn parse(
+/// The second field is the `msg =` option.
+pub struct JoinBuilderWithMessageAndDefaultValue(
+    pub PathBuf,
+    pub Option<String>,
+    pub Option<DefaultValue>,
+    pub ComponentSpans,
+);
+/// The second field indicates whether the leading `warn` flag is added, and the third field is the `msg =` option.
+pub struct JoinBuilderWithWarnMessageAndDefaultValue(
+    pub PathBuf,
+    pub bool,
+    pub Option<String>,
+    pub Option<DefaultValue>,
+    pub ComponentSpans,
+);
+
+/// The spans of the components of a path. Each span is paired with the path made of the components up to it.
+pub struct ComponentSpans(Vec<(PathBuf, Span)>);
+
+/// The value of the `default =` option.
+pub enum DefaultValue {
+    /// A literal string, which can be output in the same way as the path.
+    LitStr(String),
+    Expr(Expr),
+}
+
+#[cfg(feature = "hash")]
+/// The value of the `algo =` option.
+#[derive(Clone, Copy)]
+pub enum HashAlgorithm {
+    Sha256,
+    Sha1,
+}
+
+/// The kind of the entries given by the `files_only` or `dirs_only` flag.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum EntryKind {
+    All,
+    File,
+    Directory,
+}
+
+pub struct JoinBuilderWithDefaultValue(pub PathBuf, pub Option<DefaultValue>);
+pub struct JoinBuilderNoBeautifyWithDefaultValue(pub PathBuf, pub Option<DefaultValue>);
+/// The second field indicates whether the `strip_all` flag is added.
+pub struct JoinBuilderNoBeautifyWithStripAllAndDefaultValue(
+    pub PathBuf,
+    pub bool,
+    pub Option<DefaultValue>,
+);
+/// The second field indicates whether the `skip_root` flag is added.
+pub struct JoinBuilderNoBeautifyWithSkipRootAndDefaultValue(
+    pub PathBuf,
+    pub bool,
+    pub Option<DefaultValue>,
+);
+
+pub struct JoinBuilderWithLengthRange(pub PathBuf, pub usize, pub usize);
+/// The second field is the maximum depth to walk, which is `1` unless the `recursive` flag or the `max_depth =` option is added.
+pub struct JoinBuilderWithRecursive(pub PathBuf, pub Option<usize>);
+pub struct JoinBuilderWithStrict(pub PathBuf, pub bool);
+pub struct JoinBuilderWithRelative(pub PathBuf, pub bool);
+pub struct JoinBuilderWithStripBom(pub PathBuf, pub bool);
+/// The second field indicates whether the `respect_gitignore` flag is added, which is only allowed with the `ignore` feature.
+#[cfg_attr(not(feature = "ignore"), allow(dead_code))]
+pub struct JoinBuilderWithGitignore(pub PathBuf, pub bool);
+pub struct JoinBuilderWithLevels(pub PathBuf, pub usize);
+pub struct JoinBuilderWithEntryKind(pub PathBuf, pub EntryKind);
+pub struct JoinBuilderWithMaxDepth(pub PathBuf, pub Option<usize>);
+pub struct JoinBuilderWithMaxSize(pub PathBuf, pub u64);
+/// Multiple paths separated by commas. Each path is a literal string, or components grouped by parentheses, e.g. `("src", "lib.rs")`.
+pub struct JoinBuilderListWithDefaultValue(pub Vec<PathBuf>, pub Option<DefaultValue>);
+/// Multiple paths separated by commas. Each path is a literal string, or components grouped by parentheses, e.g. `("src", "lib.rs")`.
+pub struct JoinBuilderList(pub Vec<PathBuf>);
+pub struct JoinBuilderPair(pub PathBuf, pub PathBuf);
+/// The third field indicates whether the leading `ignore_case` flag is added.
+pub struct JoinBuilderPairWithIgnoreCase(pub PathBuf, pub PathBuf, pub bool);
+/// The second field is the file name of the index file.
+pub struct JoinBuilderWithIndex(pub PathBuf, pub String);
+/// A single path, which is a literal string or components grouped by parentheses, optionally followed by a literal string as the suffix, e.g. `basename!(("src", "lib.rs"), ".rs")`.
+pub struct JoinBuilderNoBeautifyWithSuffix(pub PathBuf, pub Option<String>);
+/// The name of an environment variable as a literal string, e.g. `getenv_path!(default = "/opt/sdk", "SDK_ROOT")`.
+pub struct JoinBuilderEnvNameWithDefaultValue(pub String, pub Option<DefaultValue>);
+/// The path is preceded by the name of an environment variable as a literal string, e.g. `env_or_manifest_path!("ASSET_ROOT", "icons")`. The first field is the name.
+pub struct JoinBuilderWithEnvName(pub String, pub PathBuf);
+/// The second field is the `env =` option, which defaults to `PROFILE`.
+pub struct JoinBuilderWithEnv(pub PathBuf, pub String);
+/// The second field is the `os =` option, and the third field is the `arch =` option.
+pub struct JoinBuilderWithTargets(pub PathBuf, pub Vec<String>, pub Vec<String>);
+/// The path is followed by a literal string, e.g. `add_extension!("assets", "style.css", "gz")`.
+pub struct JoinBuilderWithTrailingValue(pub PathBuf, pub String);
+/// The path is followed by a literal string, e.g. `extension_is!("src", "lib.rs", "rs")`. The third field is the `ignore_case` flag.
+pub struct JoinBuilderNoBeautifyWithTrailingValue(pub PathBuf, pub String, pub bool);
+pub struct JoinBuilderNoBeautifyWithTrailingValueAndDefaultValue(
+    pub PathBuf,
+    pub String,
+    pub Option<DefaultValue>,
+);
+pub struct JoinBuilderWithDenylist(pub PathBuf, pub Vec<String>);
+/// The second field is the recognized compound extensions, which are the built-in ones and the ones added by the `compounds =` option, without the leading `.`.
+pub struct JoinBuilderNoBeautifyWithCompoundsAndDefaultValue(
+    pub PathBuf,
+    pub Vec<String>,
+    pub Option<DefaultValue>,
+);
+/// The third field indicates whether the `ignore_case` flag is added.
+pub struct JoinBuilderWithExtensions(pub PathBuf, pub Vec<String>, pub bool);
+#[cfg(feature = "mime_guess")]
+/// The second field indicates whether the `skip_unknown` flag is added.
+pub struct JoinBuilderWithSkipUnknownAndDefaultValue(
+    pub PathBuf,
+    pub bool,
+    pub Option<DefaultValue>,
+);
+#[cfg(feature = "force-separator")]
+pub struct JoinBuilderWithSeparator(pub PathBuf, pub u8);
+#[cfg(feature = "hash")]
+/// The second field is the `prefix =` option.
+pub struct JoinBuilderWithPrefix(pub PathBuf, pub Option<String>);
+#[cfg(feature = "hash")]
+pub struct JoinBuilderWithHashAlgorithm(pub PathBuf, pub HashAlgorithm);
+#[cfg(feature = "hash")]
+/// The third field is the `max_depth =` option.
+pub struct JoinBuilderWithHashAlgorithmAndMaxDepth(
+    pub PathBuf,
+    pub HashAlgorithm,
+    pub Option<usize>,
+);
+#[cfg(feature = "jsonschema")]
+/// The second field is the path of the schema.
+pub struct JoinBuilderWithSchema(pub PathBuf, pub PathBuf);
+
+#[inline]
+fn ends_with_separator(path: &Path) -> bool {
+    path.to_str().and_then(|s| s.chars().last()).map(path::is_separator).unwrap_or(false)
+}
+
+#[inline]
+fn peek_keyword(input: ParseStream, name: &str) -> bool {
+    input.cursor().ident().map(|(ident, _)| ident == name).unwrap_or(false)
+}
+
+/// Parses a leading `name,` flag if it is present.
+fn parse_flag(input: ParseStream, name: &str) -> Result<bool, syn::Error> {
+    if peek_keyword(input, name) && input.peek2(Token!(,)) {
+        input.parse::<syn::Ident>()?;
+        input.parse::<Token!(,)>()?;
+
+        Ok(true)
+    } else {
+        Ok(false)
+    }
+}
+
+/// Parses a leading `keep_separators,` or `native_separators,` flag if it is present. Returns `Some(false)` or `Some(true)` respectively to override whether the separators are replaced by the `replace-separator` feature.
+fn parse_separators(input: ParseStream) -> Result<Option<bool>, syn::Error> {
+    let keep_separators = parse_flag(input, "keep_separators")?;
+    let native_separators = parse_flag(input, "native_separators")?;
+
+    match (keep_separators, native_separators) {
+        (false, false) => Ok(None),
+        (true, false) => Ok(Some(false)),
+        (false, true) => Ok(Some(true)),
+        (true, true) => Err(syn::Error::new(
+            Span::call_site(),
+            "the `keep_separators` and `native_separators` flags cannot be used together",
+        )),
+    }
+}
+
+/// Parses a leading `respect_gitignore,` flag if it is present. It is an error to add the flag without the `ignore` feature.
+fn parse_respect_gitignore(input: ParseStream) -> Result<bool, syn::Error> {
+    let respect_gitignore = parse_flag(input, "respect_gitignore")?;
+
+    if cfg!(not(feature = "ignore")) && respect_gitignore {
+        return Err(syn::Error::new(
+            Span::call_site(),
+            "the `respect_gitignore` flag requires the `ignore` feature",
+        ));
+    }
+
+    Ok(respect_gitignore)
+}
+
+/// Parses exactly two paths separated by a comma.
+fn parse_pair(input: ParseStream) -> Result<(PathBuf, PathBuf), syn::Error> {
+    let mut paths = parse_list(input, false)?.0.into_iter();
+
+    match (paths.next(), paths.next(), paths.next()) {
+        (Some(a), Some(b), None) => Ok((a, b)),
+        _ => Err(syn::Error::new(
+            Span::call_site(),
+            "exactly two paths are required, use parentheses to group the components of a path",
+        )),
+    }
+}
+
+/// Parses a leading `max_depth = <usize>,` option if it is present. The directory itself is at depth `0`.
+fn parse_max_depth(input: ParseStream) -> Result<Option<usize>, syn::Error> {
+    match parse_option::<LitInt>(input, "max_depth")? {
+        Some(lit) => Ok(Some(lit.base10_parse()?)),
+        None => Ok(None),
+    }
+}
+
+/// Parses a leading `name = value,` option if it is present.
+fn parse_option<T: Parse>(input: ParseStream, name: &str) -> Result<Option<T>, syn::Error> {
+    if peek_keyword(input, name) && input.peek2(Token!(=)) {
+        input.parse::<syn::Ident>()?;
+        input.parse::<Token!(=)>()?;
+
+        let value = input.parse::<T>()?;
+
+        input.parse::<Token!(,)>()?;
+
+        Ok(Some(value))
+    } else {
+        Ok(None)
+    }
+}
+
+/// The value of the `base =` option, which is the directory that a relative path is joined to instead of the CARGO_MANIFEST_DIR.
+struct Base(PathBuf);
+
+impl Parse for Base {
+    #[inline]
+    fn parse(input: ParseStream) -> Result<Self, syn::Error> {
+        if input.peek(LitStr) {
+            let lit = input.parse::<LitStr>()?;
+
+            let name = lit.value();
+
+            match env::var_os(&name) {
+                Some(dir) => Ok(Base(PathBuf::from(dir))),
+                None => Err(syn::Error::new(
+                    lit.span(),
+                    format!("the {} environment variable is not set", name),
+                )),
+            }
+        } else {
+            let ident = input.parse::<syn::Ident>()?;
+
+            if ident == "manifest" {
+                Ok(Base(crate::MANIFEST_DIR.clone()))
+            } else if ident == "workspace" {
+                #[cfg(feature = "workspace")]
+                {
+                    Ok(Base(crate::functions::find_workspace_root(&crate::MANIFEST_DIR)))
+                }
+
+                #[cfg(not(feature = "workspace"))]
+                {
+                    Err(syn::Error::new(
+                        ident.span(),
+                        "the `workspace` feature is required for `base = workspace`",
+                    ))
+                }
+            } else {
+                Err(syn::Error::new(
+                    ident.span(),
+                    "expected `manifest`, `workspace`, or the name of an environment variable as \
+                     a literal string",
+                ))
+            }
+        }
+    }
+}
+
+/// Joins a relative path to the directory given by the `base =` option.
+#[inline]
+fn join_base(base: Option<&Base>, path: PathBuf) -> PathBuf {
+    match base {
+        Some(base) if path.is_relative() => base.0.join(path),
+        _ => path,
+    }
+}
+
+/// A bracketed list of literal strings, like `["a", "b"]`.
+struct LitStrList(Vec<String>);
+
+impl Parse for LitStrList {
+    #[inline]
+    fn parse(input: ParseStream) -> Result<Self, syn::Error> {
+        let content;
+
+        syn::bracketed!(content in input);
+
+        let lit_strs = Punctuated::<LitStr, Token!(,)>::parse_terminated(&content)?;
+
+        Ok(LitStrList(lit_strs.into_iter().map(|lit| lit.value()).collect()))
+    }
+}
+
+/// Parses a leading `default = value,` option if it is present.
+fn parse_default_value(
     input: ParseStream,
-    default_value: bool,
     _beautify: bool,
-) -> Result<(PathBuf, Option<Expr>), syn::Error> {
-    let default_value = if default_value && input.lookahead1().peek(Token!(default)) {
+) -> Result<Option<DefaultValue>, syn::Error> {
+    if input.lookahead1().peek(Token!(default)) {
         input.parse::<Token!(default)>()?;
         input.parse::<Token!(=)>()?;
 
         let expr = input.parse::<Expr>()?;
 
-        input.parse::<Token!(,)>()?;
+        if !input.is_empty() {
+            input.parse::<Token!(,)>()?;
+        }
+
+        if input.is_empty() {
+            return Err(syn::Error::new_spanned(
+                expr,
+                "a path is required after the default value",
+            ));
+        }
+
+        match expr {
+            Expr::Lit(syn::ExprLit {
+                lit: Lit::Str(s), ..
+            }) => {
+                let s = s.value();
+
+                #[cfg(windows)]
+                let s = if _beautify { crate::functions::beautify_windows_path(s) } else { s };
+
+                Ok(Some(DefaultValue::LitStr(s)))
+            },
+            expr => Ok(Some(DefaultValue::Expr(expr))),
+        }
+    } else {
+        Ok(None)
+    }
+}
+
+/// Parses a `concat = ("a", "b", ...)` item if it is present. The literal strings in the parentheses are concatenated into one component, so the commas in the parentheses take precedence over the commas separating components.
+fn parse_concat(input: ParseStream) -> Result<Option<String>, syn::Error> {
+    if peek_keyword(input, "concat") && input.peek2(Token!(=)) {
+        input.parse::<syn::Ident>()?;
+        input.parse::<Token!(=)>()?;
 
-        Some(expr)
+        let content;
+
+        syn::parenthesized!(content in input);
+
+        let lit_strs = Punctuated::<LitStr, Token!(,)>::parse_terminated(&content)?;
+
+        Ok(Some(lit_strs.iter().map(|lit_str| lit_str.value()).collect()))
     } else {
-        None
+        Ok(None)
+    }
+}
+
+const BYTE_STR_NOT_SUPPORTED: &str = "byte string literals are not supported, use a string \
+                                      literal (`\"...\"`) or a raw string literal (`r\"...\"`) \
+                                      instead";
+
+/// Parses a literal string, or a `concat = (...)` item, as a component.
+#[inline]
+fn parse_component(input: ParseStream) -> Result<String, syn::Error> {
+    if input.peek(LitByteStr) {
+        return Err(syn::Error::new(input.span(), BYTE_STR_NOT_SUPPORTED));
+    }
+
+    match parse_concat(input)? {
+        Some(s) => Ok(s),
+        None => Ok(input.parse::<LitStr>()?.value()),
+    }
+}
+
+/// Parses comma-separated paths. Each path is a literal string, or components grouped by parentheses.
+fn parse_list(
+    input: ParseStream,
+    default_value: bool,
+) -> Result<(Vec<PathBuf>, Option<DefaultValue>), syn::Error> {
+    let separators = parse_separators(input)?;
+
+    let _beautify = separators.unwrap_or(cfg!(feature = "replace-separator"));
+
+    let base = parse_option::<Base>(input, "base")?;
+
+    let default_value = if default_value { parse_default_value(input, _beautify)? } else { None };
+
+    let mut paths = Vec::new();
+
+    while !input.is_empty() {
+        if input.peek(syn::token::Paren) {
+            let content;
+
+            syn::parenthesized!(content in input);
+
+            paths.push(join_base(
+                base.as_ref(),
+                parse_with_separators(&content, false, true, separators)?.0,
+            ));
+        } else {
+            let s = parse_component(input)?;
+
+            #[cfg(windows)]
+            let s = if _beautify { crate::functions::beautify_windows_path(s) } else { s };
+
+            let path = PathBuf::from(s);
+
+            #[cfg(feature = "home")]
+            let path = expand_home(path)?;
+
+            paths.push(join_base(base.as_ref(), path));
+        }
+
+        if input.is_empty() {
+            break;
+        }
+
+        input.parse::<Token!(,)>()?;
+    }
+
+    if paths.is_empty() {
+        return Err(syn::Error::new(Span::call_site(), "at least one path is required"));
+    }
+
+    Ok((paths, default_value))
+}
+
+/// Splits off the last comma-separated element, which must be a literal string, and parses the remaining elements as the path.
+fn parse_with_trailing_lit_str(
+    input: ParseStream,
+    beautify: bool,
+) -> Result<(PathBuf, LitStr), syn::Error> {
+    let mut exprs = Punctuated::<Expr, Token!(,)>::parse_terminated(input)?;
+
+    let lit_str = match exprs.pop() {
+        Some(pair) if !exprs.is_empty() => {
+            syn::parse2::<LitStr>(pair.into_value().into_token_stream())?
+        },
+        _ => {
+            return Err(syn::Error::new(
+                Span::call_site(),
+                "a path and a literal string are required",
+            ))
+        },
     };
 
-    let s = input.parse::<LitStr>()?.value();
+    let result =
+        (|input: ParseStream| parse(input, false, beautify)).parse2(exprs.into_token_stream())?;
+
+    Ok((result.0, lit_str))
+}
+
+/// Expands a leading `~` component of the path to the home directory of the current user.
+#[cfg(feature = "home")]
+fn expand_home(path: PathBuf) -> Result<PathBuf, syn::Error> {
+    let mut components = path.components();
+
+    match components.next() {
+        Some(path::Component::Normal(first)) if first == "~" => match home::home_dir() {
+            Some(home_dir) => Ok(home_dir.join(components.as_path())),
+            None => Err(syn::Error::new(
+                Span::call_site(),
+                "the home directory of the current user cannot be resolved",
+            )),
+        },
+        Some(path::Component::Normal(first))
+            if first.to_str().map(|s| s.starts_with('~')).unwrap_or(false) =>
+        {
+            Err(syn::Error::new(
+                Span::call_site(),
+                format!(
+                    "{:?} is not supported, only `~` can be expanded to the home directory of the \
+                     current user",
+                    first
+                ),
+            ))
+        },
+        _ => Ok(path),
+    }
+}
+
+impl ComponentSpans {
+    /// Gets the span of the first component at which the path stops existing, or the span of the last component if the whole path exists. A relative path is checked against the CARGO_MANIFEST_DIR.
+    pub fn error_span(&self) -> Span {
+        for (p, span) in self.0.iter() {
+            let exists =
+                if p.is_absolute() { p.exists() } else { crate::MANIFEST_DIR.join(p).exists() };
+
+            if !exists {
+                return *span;
+            }
+        }
+
+        self.0.last().map(|(_, span)| *span).unwrap_or_else(Span::call_site)
+    }
+}
+
+/// Expands `~` and joins the `base =` option for the path and the paths of the component spans.
+fn finish_path(
+    base: Option<&Base>,
+    path: PathBuf,
+    spans: Vec<(PathBuf, Span)>,
+) -> Result<(PathBuf, ComponentSpans), syn::Error> {
+    #[cfg(feature = "home")]
+    let path = expand_home(path)?;
+
+    let mut component_spans = Vec::with_capacity(spans.len());
 
-    #[cfg(all(windows, feature = "replace-separator"))]
-    let s = if _beautify { crate::functions::beautify_windows_path(s) } else { s };
+    for (p, span) in spans {
+        #[cfg(feature = "home")]
+        let p = expand_home(p)?;
+
+        component_spans.push((join_base(base, p), span));
+    }
+
+    Ok((join_base(base, path), ComponentSpans(component_spans)))
+}
+
+#[inline]
+fn parse(
+    input: ParseStream,
+    default_value: bool,
+    beautify: bool,
+) -> Result<(PathBuf, Option<DefaultValue>, ComponentSpans), syn::Error> {
+    parse_with_separators(input, default_value, beautify, None)
+}
+
+/// Parses a path. The `separators` is given by the `keep_separators` or `native_separators` flag outside the path, and can be overridden by the flag at the beginning of the path.
+#[cfg(not(feature = "tuple"))]
+fn parse_with_separators(
+    input: ParseStream,
+    default_value: bool,
+    beautify: bool,
+    separators: Option<bool>,
+) -> Result<(PathBuf, Option<DefaultValue>, ComponentSpans), syn::Error> {
+    let separators = parse_separators(input)?.or(separators);
+
+    // The separators of the first component are replaced only for the macros which beautify paths, while the ones of the other components are always replaced with the `replace-separator` feature.
+    let _beautify_first = separators.unwrap_or(cfg!(feature = "replace-separator") && beautify);
+    let _beautify_rest = separators.unwrap_or(cfg!(feature = "replace-separator"));
+
+    let base = parse_option::<Base>(input, "base")?;
+
+    let default_value =
+        if default_value { parse_default_value(input, _beautify_first)? } else { None };
+
+    let span = input.span();
+
+    let s = parse_component(input)?;
+
+    #[cfg(windows)]
+    let s = if _beautify_first { crate::functions::beautify_windows_path(s) } else { s };
 
     let mut path = PathBuf::from(s);
 
+    let mut spans = vec![(path.clone(), span)];
+
     loop {
         if input.is_empty() {
-            return Ok((path, default_value));
+            break;
         }
 
         input.parse::<Token!(,)>()?;
 
         if input.is_empty() {
-            return Ok((path, default_value));
+            break;
         }
 
-        let s = input.parse::<LitStr>()?.value();
+        let span = input.span();
 
-        #[cfg(all(windows, feature = "replace-separator"))]
-        let s = crate::functions::beautify_windows_path(s);
+        let s = parse_component(input)?;
+
+        #[cfg(windows)]
+        let s = if _beautify_rest { crate::functions::beautify_windows_path(s) } else { s };
 
         path.push(s);
+
+        spans.push((path.clone(), span));
     }
+
+    let (path, spans) = finish_path(base.as_ref(), path, spans)?;
+
+    Ok((path, default_value, spans))
 }
 
 #[cfg(feature = "tuple")]
-fn handle_expr(expr: Expr, path: &mut PathBuf, _beautify: bool) -> Result<(), syn::Error> {
+fn handle_expr(
+    expr: Expr,
+    path: &mut PathBuf,
+    spans: &mut Vec<(PathBuf, Span)>,
+    _beautify: bool,
+) -> Result<(), syn::Error> {
     match expr {
         Expr::Lit(lit) => {
-            if let Lit::Str(s) = lit.lit {
-                let s = s.value();
+            let span = lit.span();
 
-                #[cfg(all(windows, feature = "replace-separator"))]
-                let s = if _beautify { crate::functions::beautify_windows_path(s) } else { s };
+            match lit.lit {
+                Lit::Str(s) => {
+                    let s = s.value();
 
-                path.push(s);
-            } else {
-                return Err(syn::Error::new(lit.span(), "not a literal string"));
+                    #[cfg(windows)]
+                    let s = if _beautify { crate::functions::beautify_windows_path(s) } else { s };
+
+                    path.push(s);
+                },
+                // integers and chars are stringified, e.g. `("v", 2)` becomes `v/2`
+                Lit::Int(i) => path.push(i.base10_digits()),
+                Lit::Char(c) => path.push(c.value().to_string()),
+                Lit::ByteStr(b) => return Err(syn::Error::new(b.span(), BYTE_STR_NOT_SUPPORTED)),
+                _ => return Err(syn::Error::new(lit.span(), "not a literal string")),
             }
+
+            spans.push((path.clone(), span));
         },
         Expr::Tuple(tuple) => {
             for expr in tuple.elems {
-                handle_expr(expr, path, _beautify)?;
+                handle_expr(expr, path, spans, _beautify)?;
             }
         },
         Expr::Group(group) => {
             // In order to use the `expr` matcher in this macro. I don't know why it ends up here.
             let expr = syn::parse2::<Expr>(group.expr.into_token_stream())?;
 
-            return handle_expr(expr, path, _beautify);
+            return handle_expr(expr, path, spans, _beautify);
         },
         Expr::Paren(paren) => {
             let expr = syn::parse2::<Expr>(paren.expr.into_token_stream())?;
 
-            return handle_expr(expr, path, _beautify);
+            return handle_expr(expr, path, spans, _beautify);
         },
         _ => {
             return Err(syn::Error::new(
@@ -105,36 +662,49 @@ fn handle_expr(expr: Expr, path: &mut PathBuf, _beautify: bool) -> Result<(), sy
     Ok(())
 }
 
+/// Parses a path. The `separators` is given by the `keep_separators` or `native_separators` flag outside the path, and can be overridden by the flag at the beginning of the path.
 #[cfg(feature = "tuple")]
-fn parse(
+fn parse_with_separators(
     input: ParseStream,
     default_value: bool,
-    _beautify: bool,
-) -> Result<(PathBuf, Option<Expr>), syn::Error> {
+    beautify: bool,
+    separators: Option<bool>,
+) -> Result<(PathBuf, Option<DefaultValue>, ComponentSpans), syn::Error> {
     if input.is_empty() {
         // to hint developers that they must input some arguments
         let _ = input.parse::<LitStr>()?;
     }
 
-    let default_value = if default_value && input.lookahead1().peek(Token!(default)) {
-        input.parse::<Token!(default)>()?;
-        input.parse::<Token!(=)>()?;
+    let separators = parse_separators(input)?.or(separators);
 
-        let expr = input.parse::<Expr>()?;
+    let _beautify = separators.unwrap_or(cfg!(feature = "replace-separator") && beautify);
 
-        input.parse::<Token!(,)>()?;
+    let base = parse_option::<Base>(input, "base")?;
 
-        Some(expr)
-    } else {
-        None
-    };
+    let default_value = if default_value { parse_default_value(input, _beautify)? } else { None };
 
     let mut path = PathBuf::new();
 
+    let mut spans = Vec::new();
+
     while !input.is_empty() {
-        let expr = input.parse::<Expr>()?;
+        let span = input.span();
+
+        match parse_concat(input)? {
+            Some(s) => {
+                #[cfg(windows)]
+                let s = if _beautify { crate::functions::beautify_windows_path(s) } else { s };
+
+                path.push(s);
 
-        handle_expr(expr, &mut path, _beautify)?;
+                spans.push((path.clone(), span));
+            },
+            None => {
+                let expr = input.parse::<Expr>()?;
+
+                handle_expr(expr, &mut path, &mut spans, _beautify)?;
+            },
+        }
 
         if input.lookahead1().peek(Token!(,)) {
             input.parse::<Token!(,)>()?;
@@ -143,7 +713,19 @@ fn parse(
         }
     }
 
-    Ok((path, default_value))
+    let (path, spans) = finish_path(base.as_ref(), path, spans)?;
+
+    Ok((path, default_value, spans))
+}
+
+impl ToTokens for DefaultValue {
+    #[inline]
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        match self {
+            DefaultValue::LitStr(s) => s.to_tokens(tokens),
+            DefaultValue::Expr(expr) => expr.to_tokens(tokens),
+        }
+    }
 }
 
 impl Parse for JoinBuilder {
@@ -151,7 +733,9 @@ impl Parse for JoinBuilder {
     fn parse(input: ParseStream) -> Result<Self, syn::Error> {
         let result = parse(input, false, true)?;
 
-        Ok(JoinBuilder(result.0))
+        let trailing_separator = ends_with_separator(&result.0);
+
+        Ok(JoinBuilder(result.0, trailing_separator))
     }
 }
 
@@ -164,6 +748,53 @@ impl Parse for JoinBuilderNoBeautify {
     }
 }
 
+impl Parse for JoinBuilderWithMessage {
+    #[inline]
+    fn parse(input: ParseStream) -> Result<Self, syn::Error> {
+        let msg = parse_option::<LitStr>(input, "msg")?.map(|lit| lit.value());
+
+        let result = parse(input, false, true)?;
+
+        Ok(JoinBuilderWithMessage(result.0, msg, result.2))
+    }
+}
+
+impl Parse for JoinBuilderWithMessageAndDefaultValue {
+    #[inline]
+    fn parse(input: ParseStream) -> Result<Self, syn::Error> {
+        let msg = parse_option::<LitStr>(input, "msg")?.map(|lit| lit.value());
+
+        let result = parse(input, true, true)?;
+
+        Ok(JoinBuilderWithMessageAndDefaultValue(result.0, msg, result.1, result.2))
+    }
+}
+
+impl Parse for JoinBuilderWithWarnMessageAndDefaultValue {
+    #[inline]
+    fn parse(input: ParseStream) -> Result<Self, syn::Error> {
+        let warn = parse_flag(input, "warn")?;
+
+        let msg = parse_option::<LitStr>(input, "msg")?.map(|lit| lit.value());
+
+        let result = parse(input, true, true)?;
+
+        Ok(JoinBuilderWithWarnMessageAndDefaultValue(result.0, warn, msg, result.1, result.2))
+    }
+}
+
+impl Parse for JoinBuilderWithMessageAndPrefix {
+    #[inline]
+    fn parse(input: ParseStream) -> Result<Self, syn::Error> {
+        let msg = parse_option::<LitStr>(input, "msg")?.map(|lit| lit.value());
+        let prefix = parse_option::<LitStr>(input, "prefix")?.map(|lit| lit.value());
+
+        let result = parse(input, false, true)?;
+
+        Ok(JoinBuilderWithMessageAndPrefix(result.0, msg, prefix, result.2))
+    }
+}
+
 impl Parse for JoinBuilderWithDefaultValue {
     #[inline]
     fn parse(input: ParseStream) -> Result<Self, syn::Error> {
@@ -182,6 +813,504 @@ impl Parse for JoinBuilderNoBeautifyWithDefaultValue {
     }
 }
 
+impl Parse for JoinBuilderNoBeautifyWithStripAllAndDefaultValue {
+    #[inline]
+    fn parse(input: ParseStream) -> Result<Self, syn::Error> {
+        let strip_all = parse_flag(input, "strip_all")?;
+
+        let result = parse(input, true, false)?;
+
+        Ok(JoinBuilderNoBeautifyWithStripAllAndDefaultValue(result.0, strip_all, result.1))
+    }
+}
+
+impl Parse for JoinBuilderNoBeautifyWithSkipRootAndDefaultValue {
+    #[inline]
+    fn parse(input: ParseStream) -> Result<Self, syn::Error> {
+        let skip_root = parse_flag(input, "skip_root")?;
+
+        let result = parse(input, true, false)?;
+
+        Ok(JoinBuilderNoBeautifyWithSkipRootAndDefaultValue(result.0, skip_root, result.1))
+    }
+}
+
+impl Parse for JoinBuilderWithLengthRange {
+    #[inline]
+    fn parse(input: ParseStream) -> Result<Self, syn::Error> {
+        let min = match parse_option::<LitInt>(input, "min")? {
+            Some(lit) => lit.base10_parse()?,
+            None => 0,
+        };
+
+        let max = match parse_option::<LitInt>(input, "max")? {
+            Some(lit) => lit.base10_parse()?,
+            None => usize::MAX,
+        };
+
+        let result = parse(input, false, true)?;
+
+        Ok(JoinBuilderWithLengthRange(result.0, min, max))
+    }
+}
+
+impl Parse for JoinBuilderWithRecursive {
+    #[inline]
+    fn parse(input: ParseStream) -> Result<Self, syn::Error> {
+        let recursive = parse_flag(input, "recursive")?;
+
+        let max_depth = match parse_max_depth(input)? {
+            Some(max_depth) => Some(max_depth),
+            None if recursive => None,
+            None => Some(1),
+        };
+
+        let result = parse(input, false, true)?;
+
+        Ok(JoinBuilderWithRecursive(result.0, max_depth))
+    }
+}
+
+impl Parse for JoinBuilderWithStrict {
+    #[inline]
+    fn parse(input: ParseStream) -> Result<Self, syn::Error> {
+        let strict = parse_flag(input, "strict")?;
+
+        let result = parse(input, false, true)?;
+
+        Ok(JoinBuilderWithStrict(result.0, strict))
+    }
+}
+
+impl Parse for JoinBuilderWithRelative {
+    #[inline]
+    fn parse(input: ParseStream) -> Result<Self, syn::Error> {
+        let relative = parse_flag(input, "relative")?;
+
+        let result = parse(input, false, true)?;
+
+        Ok(JoinBuilderWithRelative(result.0, relative))
+    }
+}
+
+impl Parse for JoinBuilderWithStripBom {
+    #[inline]
+    fn parse(input: ParseStream) -> Result<Self, syn::Error> {
+        let strip_bom = parse_flag(input, "strip_bom")?;
+
+        let result = parse(input, false, true)?;
+
+        Ok(JoinBuilderWithStripBom(result.0, strip_bom))
+    }
+}
+
+impl Parse for JoinBuilderWithGitignore {
+    #[inline]
+    fn parse(input: ParseStream) -> Result<Self, syn::Error> {
+        let respect_gitignore = parse_respect_gitignore(input)?;
+
+        let result = parse(input, false, true)?;
+
+        Ok(JoinBuilderWithGitignore(result.0, respect_gitignore))
+    }
+}
+
+impl Parse for JoinBuilderWithEntryKind {
+    #[inline]
+    fn parse(input: ParseStream) -> Result<Self, syn::Error> {
+        let files_only = parse_flag(input, "files_only")?;
+        let dirs_only = parse_flag(input, "dirs_only")?;
+
+        let kind = match (files_only, dirs_only) {
+            (false, false) => EntryKind::All,
+            (true, false) => EntryKind::File,
+            (false, true) => EntryKind::Directory,
+            (true, true) => {
+                return Err(syn::Error::new(
+                    Span::call_site(),
+                    "the `files_only` and `dirs_only` flags cannot be used together",
+                ))
+            },
+        };
+
+        let result = parse(input, false, true)?;
+
+        Ok(JoinBuilderWithEntryKind(result.0, kind))
+    }
+}
+
+impl Parse for JoinBuilderWithLevels {
+    #[inline]
+    fn parse(input: ParseStream) -> Result<Self, syn::Error> {
+        let levels = match parse_option::<LitInt>(input, "levels")? {
+            Some(lit) => lit.base10_parse()?,
+            None => 1,
+        };
+
+        let result = parse(input, false, true)?;
+
+        Ok(JoinBuilderWithLevels(result.0, levels))
+    }
+}
+
+impl Parse for JoinBuilderWithMaxSize {
+    #[inline]
+    fn parse(input: ParseStream) -> Result<Self, syn::Error> {
+        let max_size = match parse_option::<LitInt>(input, "max_size")? {
+            Some(lit) => lit.base10_parse()?,
+            None => 1024 * 1024,
+        };
+
+        let result = parse(input, false, true)?;
+
+        Ok(JoinBuilderWithMaxSize(result.0, max_size))
+    }
+}
+
+impl Parse for JoinBuilderWithMaxDepth {
+    #[inline]
+    fn parse(input: ParseStream) -> Result<Self, syn::Error> {
+        let max_depth = parse_max_depth(input)?;
+
+        let result = parse(input, false, true)?;
+
+        Ok(JoinBuilderWithMaxDepth(result.0, max_depth))
+    }
+}
+
+impl Parse for JoinBuilderListWithDefaultValue {
+    #[inline]
+    fn parse(input: ParseStream) -> Result<Self, syn::Error> {
+        let result = parse_list(input, true)?;
+
+        Ok(JoinBuilderListWithDefaultValue(result.0, result.1))
+    }
+}
+
+impl Parse for JoinBuilderList {
+    #[inline]
+    fn parse(input: ParseStream) -> Result<Self, syn::Error> {
+        let result = parse_list(input, false)?;
+
+        Ok(JoinBuilderList(result.0))
+    }
+}
+
+impl Parse for JoinBuilderPair {
+    #[inline]
+    fn parse(input: ParseStream) -> Result<Self, syn::Error> {
+        let (a, b) = parse_pair(input)?;
+
+        Ok(JoinBuilderPair(a, b))
+    }
+}
+
+impl Parse for JoinBuilderPairWithIgnoreCase {
+    #[inline]
+    fn parse(input: ParseStream) -> Result<Self, syn::Error> {
+        let ignore_case = parse_flag(input, "ignore_case")?;
+
+        let (a, b) = parse_pair(input)?;
+
+        Ok(JoinBuilderPairWithIgnoreCase(a, b, ignore_case))
+    }
+}
+
+impl Parse for JoinBuilderNoBeautifyWithSuffix {
+    #[inline]
+    fn parse(input: ParseStream) -> Result<Self, syn::Error> {
+        let path = if input.peek(syn::token::Paren) {
+            let content;
+
+            syn::parenthesized!(content in input);
+
+            parse(&content, false, false)?.0
+        } else {
+            PathBuf::from(parse_component(input)?)
+        };
+
+        let suffix = if input.is_empty() {
+            None
+        } else {
+            input.parse::<Token!(,)>()?;
+
+            if input.is_empty() {
+                None
+            } else {
+                Some(input.parse::<LitStr>()?.value())
+            }
+        };
+
+        if !input.is_empty() {
+            return Err(syn::Error::new(
+                input.span(),
+                "a path and an optional suffix are expected, use parentheses to group the \
+                 components of the path",
+            ));
+        }
+
+        Ok(JoinBuilderNoBeautifyWithSuffix(path, suffix))
+    }
+}
+
+impl Parse for JoinBuilderEnvNameWithDefaultValue {
+    #[inline]
+    fn parse(input: ParseStream) -> Result<Self, syn::Error> {
+        let default_value = parse_default_value(input, true)?;
+
+        let name = input.parse::<LitStr>()?.value();
+
+        if !input.is_empty() {
+            input.parse::<Token!(,)>()?;
+        }
+
+        Ok(JoinBuilderEnvNameWithDefaultValue(name, default_value))
+    }
+}
+
+impl Parse for JoinBuilderWithEnvName {
+    #[inline]
+    fn parse(input: ParseStream) -> Result<Self, syn::Error> {
+        let name = input.parse::<LitStr>()?.value();
+
+        input.parse::<Token!(,)>()?;
+
+        let result = parse(input, false, true)?;
+
+        Ok(JoinBuilderWithEnvName(name, result.0))
+    }
+}
+
+impl Parse for JoinBuilderWithEnv {
+    #[inline]
+    fn parse(input: ParseStream) -> Result<Self, syn::Error> {
+        let name = match parse_option::<LitStr>(input, "env")? {
+            Some(lit) => lit.value(),
+            None => String::from("PROFILE"),
+        };
+
+        let result = parse(input, false, true)?;
+
+        Ok(JoinBuilderWithEnv(result.0, name))
+    }
+}
+
+impl Parse for JoinBuilderWithTargets {
+    #[inline]
+    fn parse(input: ParseStream) -> Result<Self, syn::Error> {
+        let os = parse_option::<LitStrList>(input, "os")?.map(|list| list.0).unwrap_or_default();
+        let arch =
+            parse_option::<LitStrList>(input, "arch")?.map(|list| list.0).unwrap_or_default();
+
+        let result = parse(input, false, true)?;
+
+        Ok(JoinBuilderWithTargets(result.0, os, arch))
+    }
+}
+
+impl Parse for JoinBuilderWithIndex {
+    #[inline]
+    fn parse(input: ParseStream) -> Result<Self, syn::Error> {
+        let index = match parse_option::<LitStr>(input, "index")? {
+            Some(lit) => lit.value(),
+            None => String::from("index.html"),
+        };
+
+        let result = parse(input, false, true)?;
+
+        Ok(JoinBuilderWithIndex(result.0, index))
+    }
+}
+
+impl Parse for JoinBuilderWithTrailingValue {
+    #[inline]
+    fn parse(input: ParseStream) -> Result<Self, syn::Error> {
+        let (path, lit_str) = parse_with_trailing_lit_str(input, true)?;
+
+        Ok(JoinBuilderWithTrailingValue(path, lit_str.value()))
+    }
+}
+
+impl Parse for JoinBuilderNoBeautifyWithTrailingValue {
+    #[inline]
+    fn parse(input: ParseStream) -> Result<Self, syn::Error> {
+        let ignore_case = parse_flag(input, "ignore_case")?;
+
+        let (path, lit_str) = parse_with_trailing_lit_str(input, false)?;
+
+        Ok(JoinBuilderNoBeautifyWithTrailingValue(path, lit_str.value(), ignore_case))
+    }
+}
+
+impl Parse for JoinBuilderNoBeautifyWithTrailingValueAndDefaultValue {
+    #[inline]
+    fn parse(input: ParseStream) -> Result<Self, syn::Error> {
+        let default_value = parse_default_value(input, false)?;
+
+        let (path, lit_str) = parse_with_trailing_lit_str(input, false)?;
+
+        Ok(JoinBuilderNoBeautifyWithTrailingValueAndDefaultValue(
+            path,
+            lit_str.value(),
+            default_value,
+        ))
+    }
+}
+
+impl Parse for JoinBuilderWithDenylist {
+    #[inline]
+    fn parse(input: ParseStream) -> Result<Self, syn::Error> {
+        let denylist = match parse_option::<LitStrList>(input, "deny")? {
+            Some(list) => list.0.into_iter().map(|s| s.to_lowercase()).collect(),
+            None => {
+                return Err(input.error("a leading `deny = [\"<extension>\", ...]` is required"))
+            },
+        };
+
+        let result = parse(input, false, true)?;
+
+        Ok(JoinBuilderWithDenylist(result.0, denylist))
+    }
+}
+
+impl Parse for JoinBuilderNoBeautifyWithCompoundsAndDefaultValue {
+    #[inline]
+    fn parse(input: ParseStream) -> Result<Self, syn::Error> {
+        let mut compounds: Vec<String> =
+            ["tar.gz", "tar.bz2", "tar.xz", "tar.zst"].iter().map(|s| s.to_string()).collect();
+
+        if let Some(list) = parse_option::<LitStrList>(input, "compounds")? {
+            compounds.extend(list.0.into_iter().map(|s| s.trim_start_matches('.').to_string()));
+        }
+
+        let result = parse(input, true, false)?;
+
+        Ok(JoinBuilderNoBeautifyWithCompoundsAndDefaultValue(result.0, compounds, result.1))
+    }
+}
+
+impl Parse for JoinBuilderWithExtensions {
+    #[inline]
+    fn parse(input: ParseStream) -> Result<Self, syn::Error> {
+        let ignore_case = parse_flag(input, "ignore_case")?;
+
+        let extensions = if input.peek(syn::token::Bracket) {
+            input.parse::<LitStrList>()?.0
+        } else {
+            vec![input.parse::<LitStr>()?.value()]
+        };
+
+        input.parse::<Token!(,)>()?;
+
+        let result = parse(input, false, true)?;
+
+        Ok(JoinBuilderWithExtensions(result.0, extensions, ignore_case))
+    }
+}
+
+#[cfg(feature = "mime_guess")]
+impl Parse for JoinBuilderWithSkipUnknownAndDefaultValue {
+    #[inline]
+    fn parse(input: ParseStream) -> Result<Self, syn::Error> {
+        let skip_unknown = parse_flag(input, "skip_unknown")?;
+
+        let result = parse(input, true, true)?;
+
+        Ok(JoinBuilderWithSkipUnknownAndDefaultValue(result.0, skip_unknown, result.1))
+    }
+}
+
+#[cfg(feature = "force-separator")]
+impl Parse for JoinBuilderWithSeparator {
+    #[inline]
+    fn parse(input: ParseStream) -> Result<Self, syn::Error> {
+        let separator = match parse_option::<syn::LitChar>(input, "sep")? {
+            Some(lit) => match lit.value() {
+                '/' => b'/',
+                '\\' => b'\\',
+                _ => {
+                    return Err(syn::Error::new(lit.span(), "the separator must be '/' or '\\\\'"))
+                },
+            },
+            None => return Err(input.error("a leading `sep = '/'` or `sep = '\\\\'` is required")),
+        };
+
+        let result = parse(input, false, false)?;
+
+        Ok(JoinBuilderWithSeparator(result.0, separator))
+    }
+}
+
+#[cfg(feature = "hash")]
+impl Parse for HashAlgorithm {
+    #[inline]
+    fn parse(input: ParseStream) -> Result<Self, syn::Error> {
+        let lit = input.parse::<LitStr>()?;
+
+        match lit.value().as_str() {
+            "sha256" => Ok(HashAlgorithm::Sha256),
+            "sha1" => Ok(HashAlgorithm::Sha1),
+            _ => Err(syn::Error::new(lit.span(), "the algorithm must be \"sha256\" or \"sha1\"")),
+        }
+    }
+}
+
+#[cfg(feature = "hash")]
+impl Parse for JoinBuilderWithPrefix {
+    #[inline]
+    fn parse(input: ParseStream) -> Result<Self, syn::Error> {
+        let prefix = parse_option::<LitStr>(input, "prefix")?.map(|lit| lit.value());
+
+        let result = parse(input, false, true)?;
+
+        Ok(JoinBuilderWithPrefix(result.0, prefix))
+    }
+}
+
+#[cfg(feature = "hash")]
+impl Parse for JoinBuilderWithHashAlgorithm {
+    #[inline]
+    fn parse(input: ParseStream) -> Result<Self, syn::Error> {
+        let algorithm = parse_option(input, "algo")?.unwrap_or(HashAlgorithm::Sha256);
+
+        let result = parse(input, false, true)?;
+
+        Ok(JoinBuilderWithHashAlgorithm(result.0, algorithm))
+    }
+}
+
+#[cfg(feature = "hash")]
+impl Parse for JoinBuilderWithHashAlgorithmAndMaxDepth {
+    #[inline]
+    fn parse(input: ParseStream) -> Result<Self, syn::Error> {
+        let algorithm = parse_option(input, "algo")?.unwrap_or(HashAlgorithm::Sha256);
+
+        let max_depth = parse_max_depth(input)?;
+
+        let result = parse(input, false, true)?;
+
+        Ok(JoinBuilderWithHashAlgorithmAndMaxDepth(result.0, algorithm, max_depth))
+    }
+}
+
+#[cfg(feature = "jsonschema")]
+impl Parse for JoinBuilderWithSchema {
+    #[inline]
+    fn parse(input: ParseStream) -> Result<Self, syn::Error> {
+        let schema = match parse_option::<LitStr>(input, "schema")? {
+            Some(lit) => lit.value(),
+            None => return Err(input.error("a leading `schema = \"<path>\"` is required")),
+        };
+
+        #[cfg(all(windows, feature = "replace-separator"))]
+        let schema = crate::functions::beautify_windows_path(schema);
+
+        let result = parse(input, false, true)?;
+
+        Ok(JoinBuilderWithSchema(result.0, PathBuf::from(schema)))
+    }
+}
+
 impl From<JoinBuilder> for PathBuf {
     #[inline]
     fn from(jb: JoinBuilder) -> Self {
@@ -8,35 +8,422 @@ This crate provides function-like macros to check or operate paths relative to *
 ```rust
 #[macro_use] extern crate manifest_dir_macros;
 
+println!(manifest_dir!());
+println!("{} {}", package_name!(), package_version!());
+
 println!(path!("Cargo.toml"));
 println!(path!("src/lib.rs"));
 println!(path!("src", "lib.rs"));
 println!(path!("src", "lib.rs", "/bin"));
 println!(path!("/usr"));
+println!(path!("src/"));
+println!(path!("src", concat = ("lib", ".", "rs")));
+println!(path!(r"src", r#"lib.rs"#));
+println!(concat!(path!("src", "lib"), ".rs"));
+println!(path!(base = manifest, "src"));
+println!(path!(base = "CARGO_MANIFEST_DIR", "src", "lib.rs"));
+println!(env_or_manifest_path!("ASSET_ROOT", "icons", "x.png"));
+println!(getenv_path!("CARGO_MANIFEST_DIR"));
+println!(getenv_path!(default = "/opt/sdk", "SDK_ROOT"));
+println!(exist_getenv_path!("CARGO_MANIFEST_DIR"));
+println!(try_canonicalize_path!("src", "..", "Cargo.toml"));
+println!(try_canonicalize_path!("target", "not-built-yet.bin"));
+println!(realpath_relative!("src", "..", "src", "lib.rs"));
+println!(trim_trailing_separator!("src/"));
+println!(trim_trailing_separator!("/"));
+println!(add_extension!("src", "lib.rs", "bak"));
+println!(add_extension!(".env", "gz"));
+println!(concat!(path_concat!("src", "lib"), ".rs"));
+println!(display_path!("src", "lib.rs"));
+
+println!(raw_path!("src", "lib.rs"));
+println!(exist_raw_path!("/"));
 
 println!(exist_path!("Cargo.toml"));
+// With the `warn` flag, a missing path emits a warning instead of a compile error, and the path is still returned.
+// The output becomes a block expression instead of a literal when the path is missing, so it cannot be nested in `concat!`.
+assert_eq!(exist_path!(warn, "assets", "not-added-yet.png"), path!("assets", "not-added-yet.png"));
 println!(directory_path!("src"));
 println!(not_directory_path!("Cargo.toml"));
+println!(not_existing_directory_path!("Cargo.toml"));
+println!(not_existing_directory_path!("Cargo.lock.bak"));
 println!(file_path!("Cargo.toml"));
+println!(file_path!(msg = "{} must be checked in", "Cargo.toml"));
+println!(file_path!(default = path!("Cargo.toml"), "primary.bin"));
+println!(directory_path!(default = "/tmp", "Cargo.toml"));
+
+let _: &std::path::Path = static_path!("Cargo.toml");
+println!("{:?}", exist_static_path!("Cargo.toml"));
+println!("{:?}", directory_static_path!("src"));
+println!("{:?}", not_directory_static_path!("Cargo.toml"));
+println!("{:?}", file_static_path!("Cargo.toml"));
+
+let _: String = path_string!("src", "lib.rs");
+let _: String = file_name_string!("src/lib.rs");
+let _: String = file_stem_string!("src/lib.rs");
+let _: String = extension_string!("src/lib.rs");
+let _: String = parent_string!("src/lib.rs");
+
+const CARGO_TOML: Option<&str> = try_exist_path!("Cargo.toml");
+const CARGO_LOCK_BAK: Option<&str> = try_exist_path!("Cargo.lock.bak");
+println!("{:?} {:?}", CARGO_TOML, CARGO_LOCK_BAK);
+
+println!(first_existing_path!("Cargo.lock.bak", ("src", "lib.rs"), "Cargo.toml"));
+println!(first_existing_path!(default = "/usr", "Cargo.lock.bak"));
+
+const SEARCH_PATHS: &[&str] = unique_paths!("src", ("src", "..", "src"), "examples");
+println!("{:?}", SEARCH_PATHS);
+println!(path_if_exists_else!(("config", "local.toml"), "Cargo.toml"));
 
 println!(relative_path!("Cargo.toml"));
 println!(directory_relative_path!("src"));
 println!(not_directory_relative_path!("Cargo.toml"));
 println!(file_relative_path!("Cargo.toml"));
+assert_eq!(relative_path!(prefix = "/static", "css/app.css"), "/static/css/app.css");
+assert_eq!(relative_path!(prefix = "/static", "."), "/static");
+assert_eq!(file_relative_path!(prefix = "/static", "src", "lib.rs"), "/static/src/lib.rs");
+
+assert_eq!(relative_path_from!("src", "src/lib.rs"), "lib.rs");
+assert_eq!(relative_path_from!("src", "Cargo.toml"), path_concat!("..", "Cargo.toml"));
+assert_eq!(relative_path_from!(("src", "a", "b"), "src/c"), path_concat!("..", "..", "c"));
+assert_eq!(relative_path_from!("src", "./src"), ".");
 
 println!(get_file_name!("src/lib.rs"));
 println!(get_file_name!(default = "main.rs", "/"));
+let _: &std::ffi::OsStr = get_file_name_os!("src/lib.rs");
+println!("{:?}", get_file_name_os!(default = "main.rs", "/"));
+println!("{:?}", file_names!("Cargo.toml", ("src", "lib.rs"), "src/functions.rs"));
+println!("{:?}", file_names!(default = "", "Cargo.toml", "/"));
 println!(get_file_stem!("src/lib.rs"));
 println!(get_file_stem!(default = "lib", "/"));
+println!(get_file_stem!(strip_all, "archive.tar.gz"));
 println!(get_extension!("src/lib.rs"));
 println!(get_extension!(default = "rs", "src/lib"));
+assert_eq!(compound_extension!("archive.tar.gz"), "tar.gz");
+assert_eq!(compound_extension!("src/lib.rs"), "rs");
+assert_eq!(compound_extension!(compounds = ["user.js"], "prefs.user.js"), "user.js");
+println!(extension_or_empty!("src/lib.rs"));
+println!(extension_or_empty!("LICENSE"));
+println!(first_component!("src/lib.rs"));
+println!(first_component!(skip_root, "/usr/lib"));
+println!(last_component!("src/.."));
+println!(last_component!(default = ".", ""));
+println!(file_name_with_extension!("src/lib.rs", "html"));
+println!(file_name_with_extension!(default = "index.html", "/", "html"));
 println!(get_parent!("src/lib.rs"));
 println!(get_parent!(default = "/home", "/"));
+println!(parent_dir_path!("src/lib.rs"));
+println!(parent_dir_path!(default = "/home", "/"));
+
+const _: () = assert!(depth!("src/lib.rs") == 2);
+const _: () = assert!(depth!("/usr/lib") == 2);
+const _: () = assert!(depth!("./src", "lib.rs") == 2);
+#[cfg(windows)]
+const _: () = assert!(depth!(r"C:\Windows\System32") == 2);
+
+const _: () = assert!(is_relative!("foo"));
+const _: () = assert!(!is_absolute!("foo", "bar"));
+#[cfg(unix)]
+const _: () = assert!(is_absolute!("/usr"));
+
+println!(filename_len_range!(min = 1, max = 255, "Cargo.toml"));
+println!(filename_len_range!(min = 10, max = 10, "Cargo.toml"));
+
+println!("{:?}", newest_in_dir!("src"));
+println!("{:?}", newest_in_dir!(recursive, "src"));
+
+println!(collapse_repeats!("src/src/lib.rs"));
+println!(collapse_repeats!("src", "lib.rs"));
+
+println!(lower_ext_path!("Assets/LOGO.PNG"));
+println!(lower_ext_path!("Assets", "Logo.Tar.GZ"));
+println!(lower_ext_path!("LICENSE"));
+
+println!(trim_extension!("src/lib.rs"));
+println!(trim_extension!("archive.tar.gz"));
+
+const SPLIT_EXTENSION: (&str, &str) = split_extension!("src/lib.rs");
+println!("{:?}", SPLIT_EXTENSION);
+
+const _: () = assert!(path_eq!("src", ("src", "..", "src")));
+println!("{}", path_eq!(ignore_case, "Cargo.toml", "cargo.toml"));
+
+println!(dirname!("src/lib.rs"));
+println!(dirname!("lib.rs"));
+println!(basename!("src/lib.rs"));
+println!(basename!("src/lib.rs", ".rs"));
+println!(basename!(("src", "lib.rs"), ".rs"));
+
+const _: () = assert!(relative_depth!("src", "lib.rs") == 2);
+const _: () = assert!(relative_depth!("a/../../x") == -1);
+assert_eq!(relative_depth!("a/../../x").abs(), 1);
+
+println!(verbatim_path!("src/lib.rs"));
+println!(short_path!("src/lib.rs"));
+
+#[cfg(all(
+    any(target_os = "linux", target_os = "macos", target_os = "windows"),
+    any(target_arch = "x86_64", target_arch = "aarch64")
+))]
+{
+    const ICON: &str = os_path!(
+        os = ["linux", "macos", "windows"],
+        arch = ["x86_64", "aarch64"],
+        "assets",
+        "{target_os}-{target_arch}",
+        "icon.png"
+    );
+    assert!(std::path::Path::new(ICON).ends_with(format!(
+        "assets/{}-{}/icon.png",
+        std::env::consts::OS,
+        std::env::consts::ARCH
+    )));
+}
+
+println!(assert_under_manifest!("src/../Cargo.toml"));
+println!(assert_under_manifest!("plugins", "./foo/../bar"));
+
+println!(nearest_ancestor_containing!("Cargo.toml"));
+println!(nearest_ancestor_containing!(default = "/", "a-marker-which-does-not-exist"));
+
+const _: () = assert!(!has_bom!("Cargo.toml"));
+println!(bom_kind!("Cargo.toml"));
+
+const CARGO_TOML_CONTENT: &str = include_trimmed!("Cargo.toml");
+assert!(CARGO_TOML_CONTENT.starts_with("[package]"));
+assert!(!CARGO_TOML_CONTENT.ends_with('\n'));
+assert!(read_to_string!(strip_bom, "Cargo.toml").starts_with("[package]"));
+println!("{:?}", path_list_from_file!("examples/paths.txt"));
+
+let cargo_toml_bytes = byte_array!("Cargo.toml");
+println!("{}", cargo_toml_bytes.len());
+
+println!(up_path!(levels = 1, "shared/assets"));
+println!(up_path!(levels = 2, "shared/assets"));
+
+println!(index_path!("src"));
+println!(index_path!(index = "lib.rs", "src"));
+println!(index_path!(index = "lib.rs", "Cargo.toml"));
+
+println!("{}", tree_file_count!("src"));
+println!("{}", tree_file_count!(max_depth = 1, "."));
+
+const _: () = assert!(dir_index!("tests/fixtures/dir_index", ("css", "app.css")) == 0);
+const _: () = assert!(dir_index!("tests/fixtures/dir_index", ("css", "theme.css")) == 1);
+const _: () = assert!(dir_index!("tests/fixtures/dir_index", "index.html") == 2);
+const _: () = assert!(dir_index!("tests/fixtures/dir_index", ("js", "app.js")) == 3);
+
+const _: () = assert!(!is_empty_dir!("src"));
+const _: () = assert!(!is_empty_dir!("Cargo.toml"));
+
+assert_no_case_collisions!(recursive, "src");
+assert_no_case_collisions!(recursive, max_depth = 2, "src");
+
+println!("{:?}", read_dir!("src"));
+println!("{}", child_count!("src"));
+println!("{}", child_count!(files_only, "src"));
+println!("{}", child_count!(dirs_only, "."));
+
+const _: () = assert!(extension_is!("src/lib.rs", "rs"));
+const _: () = assert!(extension_is!(ignore_case, "src", "lib.rs", "RS"));
+const _: () = assert!(!extension_is!("LICENSE", "rs"));
+
+const _: () = assert!(extension_denied!(deny = ["exe", "dll", "so"], "uploads/file.EXE"));
+const _: () = assert!(!extension_denied!(deny = ["exe", "dll", "so"], "src/lib.rs"));
+const _: () = assert!(!extension_denied!(deny = ["exe"], "LICENSE"));
+println!(assert_not_denied!(deny = ["exe", "dll", "so"], "src/lib.rs"));
+
+println!(assert_extension!("rs", "src/lib.rs"));
+println!(assert_extension!(ignore_case, ["toml", "json"], "Cargo.TOML"));
+
+{
+    use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+
+    // The token can be decoded back to the relative path, whose separators are always `/`.
+    assert_eq!(URL_SAFE_NO_PAD.decode(path_token!("src/lib.rs")).unwrap(), b"src/lib.rs");
+    assert_eq!(URL_SAFE_NO_PAD.decode(path_token!("src", "lib.rs")).unwrap(), b"src/lib.rs");
+}
+
+const MODIFIED_TIME: u64 = modified_time!("Cargo.toml");
+assert_eq!(
+    MODIFIED_TIME,
+    std::fs::metadata(path!("Cargo.toml"))
+        .unwrap()
+        .modified()
+        .unwrap()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+);
+println!("{}", modified_time!(default = 0, "Cargo.lock.bak"));
+
+println!("{}", symlink_component_count!("src/lib.rs"));
+
+// `tests/fixtures/symlink/link-dir` links to `dir`, and `dir/link-file.txt` links to `file.txt`.
+#[cfg(unix)]
+const _: () = assert!(
+    symlink_component_count!("tests/fixtures/symlink/link-dir/link-file.txt")
+        == symlink_component_count!(".") + 2
+);
+
+const CARGO_TOML_LINK_PAIR: (&str, &str) = link_pair!("Cargo.toml");
+assert_eq!(CARGO_TOML_LINK_PAIR.0, CARGO_TOML_LINK_PAIR.1);
+
+#[cfg(unix)]
+{
+    const LINK_DIR_PAIR: (&str, &str) = link_pair!(strict, "tests/fixtures/symlink/link-dir");
+    assert_eq!(
+        LINK_DIR_PAIR,
+        (path!("tests/fixtures/symlink/link-dir"), path!("tests/fixtures/symlink/dir"))
+    );
+}
+
+println!("{:?}", breadcrumbs!("src/bin/tool.rs"));
+println!("{:?}", breadcrumbs!("Cargo.toml"));
+
+// Macros which output numbers emit suffixed literals, so they can be used in `const` items.
+const DEPTH: usize = depth!("src/lib.rs");
+const SYMLINK_COMPONENT_COUNT: usize = symlink_component_count!("src/lib.rs");
+const NEWEST: (&str, u64) = newest_in_dir!("src");
+println!("{} {} {:?}", DEPTH, SYMLINK_COMPONENT_COUNT, NEWEST);
 
 #[cfg(feature = "mime_guess")]
 {
-    println!(mime_guess!("src/lib.rs"));
+    assert_eq!(mime_guess!("src/lib.rs"), "text/x-rust");
     println!(mime_guess!(default = "application/octet-stream", "Cargo.lock"));
+    assert_eq!(mime_guess!("Cargo.toml"), "text/x-toml");
+    assert_eq!(
+        mime_guess!(default = "application/octet-stream", "LICENSE"),
+        "application/octet-stream"
+    );
+    assert_eq!(mime_guess!("Makefile"), "text/x-makefile");
+    assert_eq!(mime_guess!(".gitignore"), "text/plain");
+    println!("{:?}", mime_guess_all!("src/lib.rs"));
+    println!("{:?}", mime_guess_all!(default = "application/octet-stream", "LICENSE"));
+
+    // `mime_guess_typed!` needs `mime = "0.3"` in the dependencies.
+    let _: mime::Mime = mime_guess_typed!("src/lib.rs");
+    let _: mime::Mime = mime_guess_typed!(default = "application/octet-stream", "LICENSE");
+    println!("{:?}", dir_mime_table!(recursive, "src"));
+    println!("{:?}", mime_map!("src"));
+    println!("{:?}", mime_map!(skip_unknown, "."));
+    println!("{:?}", mime_map!(default = "application/octet-stream", "."));
+    assert_eq!(content_extension!(default = "unknown", "Cargo.toml"), "unknown");
+    assert_eq!(
+        content_extension!("tests/fixtures/content/photo"),
+        path!("tests/fixtures/content/photo.png")
+    );
+    assert_eq!(
+        content_extension!("tests/fixtures/content/icon.png"),
+        path!("tests/fixtures/content/icon.png")
+    );
+    assert_eq!(
+        content_extension!("tests/fixtures/content/archive.tar"),
+        path!("tests/fixtures/content/archive.tar.gz")
+    );
+    assert_eq!(
+        content_extension!("tests/fixtures/content/document"),
+        path!("tests/fixtures/content/document.pdf")
+    );
+    assert_eq!(
+        content_extension!("tests/fixtures/content/module"),
+        path!("tests/fixtures/content/module.wasm")
+    );
+}
+
+#[cfg(feature = "jsonschema")]
+{
+    println!(validate_schema!(
+        schema = "tests/fixtures/schema/config.schema.json",
+        "tests/fixtures/schema/valid.json"
+    ));
+}
+
+#[cfg(feature = "force-separator")]
+{
+    println!(path_with_separator!(sep = '\\', "src/lib.rs"));
+    println!(path_with_separator!(sep = '/', "src", "lib.rs"));
+}
+
+#[cfg(feature = "hash")]
+{
+    println!("{}", content_hash!("Cargo.toml"));
+    println!("{}", content_hash!(algo = "sha1", "Cargo.toml"));
+
+    const CONTENT_HASH: &str = content_hash!("Cargo.toml");
+    println!("{}", CONTENT_HASH);
+
+    println!("{}", tree_hash!("src"));
+    println!("{}", tree_hash!(algo = "sha1", "src"));
+    println!("{}", tree_hash!(max_depth = 1, "src"));
+
+    println!("{}", cachebust_url!("src/lib.rs"));
+    println!("{}", cachebust_url!(prefix = "/static", "src", "lib.rs"));
+
+    // The SHA-256 digest of `tests/fixtures/cachebust/app.css` starts with `535edfaf`.
+    assert_eq!(
+        cachebust_url!("tests/fixtures/cachebust/app.css"),
+        "tests/fixtures/cachebust/app.css?v=535edfaf"
+    );
+    assert_eq!(
+        cachebust_url!(prefix = "/static", "tests", "fixtures", "cachebust", "app.css"),
+        "/static/tests/fixtures/cachebust/app.css?v=535edfaf"
+    );
+}
+
+// With the `replace-separator` feature, only the separators of Windows paths are replaced, so the prefixes are kept.
+#[cfg(all(windows, feature = "replace-separator"))]
+{
+    assert_eq!(path!("C:/foo/bar"), r"C:\foo\bar");
+    assert_eq!(path!(r"\\server/share/foo"), r"\\server\share\foo");
+    assert_eq!(path_concat!("C:relative/path"), r"C:relative\path");
+    assert_eq!(path_concat!(r"C:\foo", r#"bar/baz"#), r"C:\foo\bar\baz");
+    assert_eq!(path_concat!(keep_separators, "C:/foo", "bar/baz"), r"C:/foo\bar/baz");
+}
+
+// Without the `replace-separator` feature, the input is kept as it is.
+#[cfg(all(windows, not(feature = "replace-separator")))]
+{
+    assert_eq!(path_concat!(r"C:\foo", r#"bar/baz"#), r"C:\foo\bar/baz");
+    assert_eq!(path_concat!(native_separators, r"C:\foo", "bar/baz"), r"C:\foo\bar\baz");
+}
+
+// The `ignore` feature lets `read_dir!` exclude the entries which are ignored by `.gitignore` files.
+#[cfg(feature = "ignore")]
+{
+    let entries: &[&str] = read_dir!(respect_gitignore, ".");
+    assert!(entries.contains(&path!("Cargo.toml")));
+    assert!(!entries.contains(&path!(".git")));
+    assert!(!entries.contains(&path!("target")));
+
+    // `.hidden` is hidden, and `backup.bak` and `build` are ignored by `tests/fixtures/ignore/.gitignore`.
+    assert_eq!(
+        read_dir!(respect_gitignore, "tests/fixtures/ignore"),
+        &[path!("tests/fixtures/ignore/kept.txt")]
+    );
+}
+
+#[cfg(feature = "glob")]
+{
+    const RS_FILE_COUNT: usize = glob_count!("src/**/*.rs");
+    println!("{}", RS_FILE_COUNT);
+
+    let sources: &[&str] = glob_include_str!("src", "*.rs");
+    println!("{}", sources.len());
+}
+
+// The `home` feature expands a leading `~` to the home directory of the current user. `~user` is not supported.
+#[cfg(feature = "home")]
+{
+    println!(path!("~"));
+    println!(path!("~/.cargo"));
+    println!(path!("~", ".cargo", "bin"));
+}
+
+// The `workspace` feature enables `base = workspace`, which joins a relative path to the root directory of the workspace.
+#[cfg(feature = "workspace")]
+{
+    println!(path!(base = workspace, "Cargo.toml"));
 }
 
 // The `tuple` feature lets these macros above support to input nested literal string tuples, which is useful when you want to use these macros inside a `macro_rule!` macro and concatenate with other literal strings.
@@ -49,20 +436,57 @@ println!(get_parent!(default = "/home", "/"));
     println!(path!(("foo", "bar"), "a"));
     println!(path!(("foo", "bar"), ("a", "b")));
     println!(path!(("foo", "bar", ("a", "b")), ("c", "d")));
+    println!(path!(("v", 2), ('a', "b")));
+}
+```
+
+## Build Scripts
+
+Cargo only sets the `PROFILE` environment variable for running build scripts, so `profile_path!` needs your build script to pass it to the compiler of your crate. `out_dir_relative!` needs the `OUT_DIR` environment variable, which is set when your crate has a build script.
+
+```rust,ignore
+// build.rs
+fn main() {
+    println!("cargo:rustc-env=PROFILE={}", std::env::var("PROFILE").unwrap());
 }
 ```
+
+```rust,ignore
+#[macro_use] extern crate manifest_dir_macros;
+
+println!(profile_path!("assets/{profile}/config.json"));
+
+include!(concat!(env!("OUT_DIR"), "/", out_dir_relative!("generated/mod.rs")));
+```
+
+## Minimum Supported Rust Version
+
+The crate supports Rust 1.60 with the default features. Some optional features depend on crates which need a newer compiler, with the latest versions of those crates:
+
+| Feature      | Rust |
+|--------------|------|
+| `jsonschema` | 1.83 |
+| `home`       | 1.88 |
+| `ignore`     | 1.88 |
+| `glob`       | 1.63 |
+| `workspace`  | 1.85 |
+
+`--all-features` is not tested with Rust 1.60 and does not build with it, because it needs the newest version in the table. Each optional feature in the table is tested with its own version in CI instead.
 */
 
 mod functions;
 mod join_builder;
 
-use std::{env, path::PathBuf};
+use std::{
+    env,
+    ffi::OsStr,
+    path::{Component, Path, PathBuf},
+};
 
 use functions::*;
 use join_builder::*;
 use once_cell::sync::Lazy;
 use proc_macro::TokenStream;
-#[cfg(feature = "mime_guess")]
 use quote::quote;
 use syn::parse_macro_input;
 
@@ -77,40 +501,120 @@ static MANIFEST_DIR: Lazy<PathBuf> = Lazy::new(|| {
 
 /// Allows input an absolute path, or a relative path. If a relative path is input, it will be relative to the CARGO_MANIFEST_DIR (a directory where your `Cargo.toml` located). Returns an absolute path.
 ///
+/// If the path ends with a path separator, such as `path!("src/")`, it must be an existing directory like `directory_path!`.
+///
+/// The output is a single string literal token, so it can be nested in `concat!`.
+///
+/// A relative path can be joined to a directory other than the CARGO_MANIFEST_DIR by the leading `base = <base>` option, which is accepted by every macro in this crate. `<base>` can be `manifest`, `workspace` (the root directory of the workspace, which needs the `workspace` feature), or the name of an environment variable as a literal string, such as `path!(base = "OUT_DIR", "gen.rs")`.
+///
+/// On Windows, whether `/` in the input is replaced with `\` is decided by the `replace-separator` feature. It can be overridden for one invocation by the leading `keep_separators` flag, which keeps the separators as they are input, or the leading `native_separators` flag, which replaces them even without the feature. These flags come before the `base =` option and are accepted by every macro in this crate.
+///
+/// With the `canonical-case` feature, if the path exists, every component of it is replaced with the casing which is actually used on the disk. It depends on the file system, so the output may change after a file is renamed only in case.
+///
+/// A path which contains a NUL byte is rejected by every macro in this crate, because it cannot be passed to the APIs of the operating system.
+///
+/// ```compile_fail
+/// #[macro_use] extern crate manifest_dir_macros;
+///
+/// println!(path!("src", "lib\u{0}.rs"));
+/// ```
+///
+/// So is a NUL byte which comes from a component of a tuple.
+///
+#[cfg_attr(feature = "tuple", doc = "```compile_fail")]
+#[cfg_attr(not(feature = "tuple"), doc = "```ignore")]
+/// #[macro_use] extern crate manifest_dir_macros;
+///
+/// println!(path!("src", ("lib", "\u{0}", ".rs")));
+/// ```
+///
 /// Multiple components can be input by using commas to separate them.
 #[proc_macro]
 pub fn path(input: TokenStream) -> TokenStream {
-    let original_path: PathBuf = syn::parse_macro_input!(input as JoinBuilder).into();
+    let jb = syn::parse_macro_input!(input as JoinBuilder);
 
-    let p =
-        if original_path.is_absolute() { original_path } else { MANIFEST_DIR.join(original_path) };
+    let p = if jb.0.is_absolute() { jb.0 } else { MANIFEST_DIR.join(jb.0) };
 
-    output_path(p)
+    #[cfg(feature = "canonical-case")]
+    let p = if p.exists() { find_path_case_insensitively(&p).unwrap_or(p) } else { p };
+
+    if jb.1 && !p.is_dir() {
+        compile_error_not_directory(p)
+    } else {
+        output_path(p)
+    }
 }
 
 /// Allows input an absolute path, or a relative path. (multiple components are supported) If a relative path is input, it will be relative to the CARGO_MANIFEST_DIR (a directory where your `Cargo.toml` located). Returns an absolute path, and it must exist.
 ///
+/// With the `case-insensitive` feature, the casing of the path must also be the same as the casing on the disk, even if the file system is case-insensitive.
+///
+/// A custom error message can be set by the leading `msg = "<message>"` option, where `{}` is replaced with the resolved path.
+///
+/// If the check fails, the default value set by the `default = <value>` option after the `msg` option will be used instead of showing a compile error. The default value can be another macro call which returns a `&str`, e.g. `file_path!(default = path!("fallback.bin"), "primary.bin")`.
+///
+/// Add a leading `warn` flag, which comes before the `msg` option, to emit a warning instead of a compile error if the path does not exist, and the path is still returned. Custom warnings are not supported on stable Rust, so the warning is shown as the use of a deprecated function whose note is the message, and it can be silenced by `#[allow(deprecated)]`. Like any other warning, it is not shown when the crate is compiled as a dependency of another crate. With the flag, the output is a block expression instead of a literal if the path does not exist, so it cannot be nested in `concat!`.
+///
 /// Multiple components can be input by using commas to separate them.
 #[proc_macro]
 pub fn exist_path(input: TokenStream) -> TokenStream {
-    let original_path: PathBuf = parse_macro_input!(input as JoinBuilder).into();
+    let JoinBuilderWithWarnMessageAndDefaultValue(original_path, warn, msg, default_value, spans) =
+        parse_macro_input!(input as JoinBuilderWithWarnMessageAndDefaultValue);
 
     let p =
         if original_path.is_absolute() { original_path } else { MANIFEST_DIR.join(original_path) };
 
+    #[cfg(feature = "case-insensitive")]
+    if let Some(actual_path) = find_path_case_insensitively(&p) {
+        if actual_path != p {
+            return output_default_or_error(
+                default_value.as_ref(),
+                msg.as_deref(),
+                spans.error_span(),
+                p,
+                |p| {
+                    compile_error(format!(
+                        "The path {:?} does not match the casing on the disk, which is {:?}",
+                        p, actual_path
+                    ))
+                },
+            );
+        }
+    }
+
     if p.exists() {
         output_path(p)
+    } else if let Some(default_value) = default_value {
+        output_default_path(&default_value)
+    } else if warn {
+        let warning = match msg {
+            Some(msg) => msg.replace("{}", &p.display().to_string()),
+            None => format!("The path {:?} does not exist", p),
+        };
+
+        output_path_with_warning(&p, &warning, spans.error_span())
     } else {
-        compile_error_not_exist(p)
+        compile_error_with_message(msg.as_deref(), spans.error_span(), p, compile_error_not_exist)
     }
 }
 
 /// Allows input an absolute path, or a relative path. If a relative path is input, it will be relative to the CARGO_MANIFEST_DIR (a directory where your `Cargo.toml` located). Returns an absolute path, and it must be an existing directory.
 ///
+/// A custom error message can be set by the leading `msg = "<message>"` option, where `{}` is replaced with the resolved path.
+///
+/// ```compile_fail
+/// #[macro_use] extern crate manifest_dir_macros;
+///
+/// println!(directory_path!(msg = "{} must be a directory of assets", "Cargo.toml"));
+/// ```
+///
+/// If the check fails, the default value set by the `default = <value>` option after the `msg` option will be used instead of showing a compile error. The default value can be another macro call which returns a `&str`, e.g. `file_path!(default = path!("fallback.bin"), "primary.bin")`.
+///
 /// Multiple components can be input by using commas to separate them.
 #[proc_macro]
 pub fn directory_path(input: TokenStream) -> TokenStream {
-    let original_path: PathBuf = parse_macro_input!(input as JoinBuilder).into();
+    let JoinBuilderWithMessageAndDefaultValue(original_path, msg, default_value, spans) =
+        parse_macro_input!(input as JoinBuilderWithMessageAndDefaultValue);
 
     let p =
         if original_path.is_absolute() { original_path } else { MANIFEST_DIR.join(original_path) };
@@ -118,33 +622,61 @@ pub fn directory_path(input: TokenStream) -> TokenStream {
     if p.is_dir() {
         output_path(p)
     } else {
-        compile_error_not_directory(p)
+        output_default_or_error(
+            default_value.as_ref(),
+            msg.as_deref(),
+            spans.error_span(),
+            p,
+            compile_error_not_directory,
+        )
     }
 }
 
 /// Allows input an absolute path, or a relative path. If a relative path is input, it will be relative to the CARGO_MANIFEST_DIR (a directory where your `Cargo.toml` located). Returns an absolute path, and it must not be an existing directory.
 ///
+/// A custom error message can be set by the leading `msg = "<message>"` option, where `{}` is replaced with the resolved path.
+///
+/// If the check fails, the default value set by the `default = <value>` option after the `msg` option will be used instead of showing a compile error. The default value can be another macro call which returns a `&str`, e.g. `file_path!(default = path!("fallback.bin"), "primary.bin")`.
+///
 /// Multiple components can be input by using commas to separate them.
 #[proc_macro]
 pub fn not_directory_path(input: TokenStream) -> TokenStream {
-    let original_path: PathBuf = parse_macro_input!(input as JoinBuilder).into();
+    let JoinBuilderWithMessageAndDefaultValue(original_path, msg, default_value, spans) =
+        parse_macro_input!(input as JoinBuilderWithMessageAndDefaultValue);
 
     let p =
         if original_path.is_absolute() { original_path } else { MANIFEST_DIR.join(original_path) };
 
-    if p.metadata().map(|m| !m.is_dir()).unwrap_or(false) {
-        output_path(p)
-    } else {
-        compile_error_directory(p)
+    match p.metadata() {
+        Ok(metadata) if metadata.is_dir() => output_default_or_error(
+            default_value.as_ref(),
+            msg.as_deref(),
+            spans.error_span(),
+            p,
+            compile_error_directory,
+        ),
+        Ok(_) => output_path(p),
+        Err(_) => output_default_or_error(
+            default_value.as_ref(),
+            msg.as_deref(),
+            spans.error_span(),
+            p,
+            compile_error_not_exist,
+        ),
     }
 }
 
 /// Allows input an absolute path, or a relative path. If a relative path is input, it will be relative to the CARGO_MANIFEST_DIR (a directory where your `Cargo.toml` located). Returns an absolute path, and it must be an existing file.
 ///
+/// A custom error message can be set by the leading `msg = "<message>"` option, where `{}` is replaced with the resolved path.
+///
+/// If the check fails, the default value set by the `default = <value>` option after the `msg` option will be used instead of showing a compile error. The default value can be another macro call which returns a `&str`, e.g. `file_path!(default = path!("fallback.bin"), "primary.bin")`.
+///
 /// Multiple components can be input by using commas to separate them.
 #[proc_macro]
 pub fn file_path(input: TokenStream) -> TokenStream {
-    let original_path: PathBuf = parse_macro_input!(input as JoinBuilder).into();
+    let JoinBuilderWithMessageAndDefaultValue(original_path, msg, default_value, spans) =
+        parse_macro_input!(input as JoinBuilderWithMessageAndDefaultValue);
 
     let p =
         if original_path.is_absolute() { original_path } else { MANIFEST_DIR.join(original_path) };
@@ -152,187 +684,341 @@ pub fn file_path(input: TokenStream) -> TokenStream {
     if p.is_file() {
         output_path(p)
     } else {
-        compile_error_not_file(p)
+        output_default_or_error(
+            default_value.as_ref(),
+            msg.as_deref(),
+            spans.error_span(),
+            p,
+            compile_error_not_file,
+        )
     }
 }
 
 /// Allows input a relative path. It will be relative to the CARGO_MANIFEST_DIR (a directory where your `Cargo.toml` located). Returns an absolute path.
 ///
+/// A custom error message can be set by the leading `msg = "<message>"` option, where `{}` is replaced with the resolved path.
+///
+/// A URL prefix can be set by the `prefix = "<prefix>"` option after the `msg` option. If it is set, the relative path (normalized lexically) joined to the prefix with `/` is returned instead of the absolute path, e.g. `relative_path!(prefix = "/static", "css/app.css")` returns `"/static/css/app.css"`. The path is always joined with `/` no matter which separator feature is enabled. If the path is normalized to the CARGO_MANIFEST_DIR itself, such as `"."`, the prefix is returned as it is, e.g. `relative_path!(prefix = "/static", ".")` returns `"/static"`. A path which leaves the CARGO_MANIFEST_DIR, such as `"../x"`, cannot be joined to a prefix.
+///
+/// ```compile_fail
+/// #[macro_use] extern crate manifest_dir_macros;
+///
+/// println!(relative_path!(prefix = "/static", "..", "x"));
+/// ```
+///
 /// Multiple components can be input by using commas to separate them.
 #[proc_macro]
 pub fn relative_path(input: TokenStream) -> TokenStream {
-    let original_path: PathBuf = parse_macro_input!(input as JoinBuilder).into();
+    let JoinBuilderWithMessageAndPrefix(original_path, msg, prefix, spans) =
+        parse_macro_input!(input as JoinBuilderWithMessageAndPrefix);
 
     if original_path.is_relative() {
-        output_path(MANIFEST_DIR.join(original_path))
+        let p = MANIFEST_DIR.join(&original_path);
+
+        output_path_with_prefix(prefix.as_deref(), &original_path, &p)
     } else {
-        compile_error_not_relative(original_path)
+        compile_error_with_message(
+            msg.as_deref(),
+            spans.error_span(),
+            original_path,
+            compile_error_not_relative,
+        )
     }
 }
 
 /// Allows input a relative path. It will be relative to the CARGO_MANIFEST_DIR (a directory where your `Cargo.toml` located). Returns an absolute path, and it must exist.
 ///
+/// A custom error message can be set by the leading `msg = "<message>"` option, where `{}` is replaced with the resolved path.
+///
+/// Supports the `prefix = "<prefix>"` option like `relative_path!`, e.g. `exist_relative_path!(prefix = "/static", "css/app.css")`.
+///
 /// Multiple components can be input by using commas to separate them.
 #[proc_macro]
 pub fn exist_relative_path(input: TokenStream) -> TokenStream {
-    let original_path: PathBuf = parse_macro_input!(input as JoinBuilder).into();
+    let JoinBuilderWithMessageAndPrefix(original_path, msg, prefix, spans) =
+        parse_macro_input!(input as JoinBuilderWithMessageAndPrefix);
 
     if original_path.is_relative() {
-        let p = MANIFEST_DIR.join(original_path);
+        let p = MANIFEST_DIR.join(&original_path);
 
         if p.exists() {
-            output_path(p)
+            output_path_with_prefix(prefix.as_deref(), &original_path, &p)
         } else {
-            compile_error_not_exist(p)
+            compile_error_with_message(
+                msg.as_deref(),
+                spans.error_span(),
+                p,
+                compile_error_not_exist,
+            )
         }
     } else {
-        compile_error_not_relative(original_path)
+        compile_error_with_message(
+            msg.as_deref(),
+            spans.error_span(),
+            original_path,
+            compile_error_not_relative,
+        )
     }
 }
 
 /// Allows input a relative path. It will be relative to the CARGO_MANIFEST_DIR (a directory where your `Cargo.toml` located). Returns an absolute path, and it must be a directory.
 ///
+/// A custom error message can be set by the leading `msg = "<message>"` option, where `{}` is replaced with the resolved path.
+///
+/// Supports the `prefix = "<prefix>"` option like `relative_path!`, e.g. `directory_relative_path!(prefix = "/static", "css")`.
+///
 /// Multiple components can be input by using commas to separate them.
 #[proc_macro]
 pub fn directory_relative_path(input: TokenStream) -> TokenStream {
-    let original_path: PathBuf = parse_macro_input!(input as JoinBuilder).into();
+    let JoinBuilderWithMessageAndPrefix(original_path, msg, prefix, spans) =
+        parse_macro_input!(input as JoinBuilderWithMessageAndPrefix);
 
     if original_path.is_relative() {
-        let p = MANIFEST_DIR.join(original_path);
+        let p = MANIFEST_DIR.join(&original_path);
 
         if p.is_dir() {
-            output_path(p)
+            output_path_with_prefix(prefix.as_deref(), &original_path, &p)
         } else {
-            compile_error_not_directory(p)
+            compile_error_with_message(
+                msg.as_deref(),
+                spans.error_span(),
+                p,
+                compile_error_not_directory,
+            )
         }
     } else {
-        compile_error_not_relative(original_path)
+        compile_error_with_message(
+            msg.as_deref(),
+            spans.error_span(),
+            original_path,
+            compile_error_not_relative,
+        )
     }
 }
 
 /// Allows input a relative path. It will be relative to the CARGO_MANIFEST_DIR (a directory where your `Cargo.toml` located). Returns an absolute path, and it must not be a directory.
 ///
+/// A custom error message can be set by the leading `msg = "<message>"` option, where `{}` is replaced with the resolved path.
+///
+/// Supports the `prefix = "<prefix>"` option like `relative_path!`, e.g. `not_directory_relative_path!(prefix = "/static", "css/app.css")`.
+///
 /// Multiple components can be input by using commas to separate them.
 #[proc_macro]
 pub fn not_directory_relative_path(input: TokenStream) -> TokenStream {
-    let original_path: PathBuf = parse_macro_input!(input as JoinBuilder).into();
+    let JoinBuilderWithMessageAndPrefix(original_path, msg, prefix, spans) =
+        parse_macro_input!(input as JoinBuilderWithMessageAndPrefix);
 
     if original_path.is_relative() {
-        let p = MANIFEST_DIR.join(original_path);
-
-        if p.metadata().map(|m| !m.is_dir()).unwrap_or(false) {
-            output_path(p)
-        } else {
-            compile_error_directory(p)
+        let p = MANIFEST_DIR.join(&original_path);
+
+        match p.metadata() {
+            Ok(metadata) if metadata.is_dir() => compile_error_with_message(
+                msg.as_deref(),
+                spans.error_span(),
+                p,
+                compile_error_directory,
+            ),
+            Ok(_) => output_path_with_prefix(prefix.as_deref(), &original_path, &p),
+            Err(_) => compile_error_with_message(
+                msg.as_deref(),
+                spans.error_span(),
+                p,
+                compile_error_not_exist,
+            ),
         }
     } else {
-        compile_error_not_relative(original_path)
+        compile_error_with_message(
+            msg.as_deref(),
+            spans.error_span(),
+            original_path,
+            compile_error_not_relative,
+        )
     }
 }
 
 /// Allows input a relative path. It will be relative to the CARGO_MANIFEST_DIR (a directory where your `Cargo.toml` located). Returns an absolute path, and it must be a file.
 ///
+/// A custom error message can be set by the leading `msg = "<message>"` option, where `{}` is replaced with the resolved path.
+///
+/// Supports the `prefix = "<prefix>"` option like `relative_path!`, e.g. `file_relative_path!(prefix = "/static", "css/app.css")`.
+///
 /// Multiple components can be input by using commas to separate them.
 #[proc_macro]
 pub fn file_relative_path(input: TokenStream) -> TokenStream {
-    let original_path: PathBuf = parse_macro_input!(input as JoinBuilder).into();
+    let JoinBuilderWithMessageAndPrefix(original_path, msg, prefix, spans) =
+        parse_macro_input!(input as JoinBuilderWithMessageAndPrefix);
 
     if original_path.is_relative() {
-        let p = MANIFEST_DIR.join(original_path);
+        let p = MANIFEST_DIR.join(&original_path);
 
         if p.is_file() {
-            output_path(p)
+            output_path_with_prefix(prefix.as_deref(), &original_path, &p)
         } else {
-            compile_error_not_file(p)
+            compile_error_with_message(
+                msg.as_deref(),
+                spans.error_span(),
+                p,
+                compile_error_not_file,
+            )
         }
     } else {
-        compile_error_not_relative(original_path)
+        compile_error_with_message(
+            msg.as_deref(),
+            spans.error_span(),
+            original_path,
+            compile_error_not_relative,
+        )
     }
 }
 
 /// Allows input a absolute path. Checks and returns the absolute path.
 ///
+/// A custom error message can be set by the leading `msg = "<message>"` option, where `{}` is replaced with the resolved path.
+///
 /// Multiple components can be input by using commas to separate them.
 #[proc_macro]
 pub fn absolute_path(input: TokenStream) -> TokenStream {
-    let original_path: PathBuf = parse_macro_input!(input as JoinBuilder).into();
+    let JoinBuilderWithMessage(original_path, msg, spans) =
+        parse_macro_input!(input as JoinBuilderWithMessage);
 
     if original_path.is_absolute() {
         output_path(original_path)
     } else {
-        compile_error_not_absolute(original_path)
+        compile_error_with_message(
+            msg.as_deref(),
+            spans.error_span(),
+            original_path,
+            compile_error_not_absolute,
+        )
     }
 }
 
 /// Allows input a absolute path. Checks whether it exists and returns the absolute path.
 ///
+/// A custom error message can be set by the leading `msg = "<message>"` option, where `{}` is replaced with the resolved path.
+///
 /// Multiple components can be input by using commas to separate them.
 #[proc_macro]
 pub fn exist_absolute_path(input: TokenStream) -> TokenStream {
-    let original_path: PathBuf = parse_macro_input!(input as JoinBuilder).into();
+    let JoinBuilderWithMessage(original_path, msg, spans) =
+        parse_macro_input!(input as JoinBuilderWithMessage);
 
     if original_path.is_absolute() {
         if original_path.exists() {
             output_path(original_path)
         } else {
-            compile_error_not_exist(original_path)
+            compile_error_with_message(
+                msg.as_deref(),
+                spans.error_span(),
+                original_path,
+                compile_error_not_exist,
+            )
         }
     } else {
-        compile_error_not_absolute(original_path)
+        compile_error_with_message(
+            msg.as_deref(),
+            spans.error_span(),
+            original_path,
+            compile_error_not_absolute,
+        )
     }
 }
 
 /// Allows input a absolute path. Checks whether it is a directory and returns the absolute path.
 ///
+/// A custom error message can be set by the leading `msg = "<message>"` option, where `{}` is replaced with the resolved path.
+///
 /// Multiple components can be input by using commas to separate them.
 #[proc_macro]
 pub fn directory_absolute_path(input: TokenStream) -> TokenStream {
-    let original_path: PathBuf = parse_macro_input!(input as JoinBuilder).into();
+    let JoinBuilderWithMessage(original_path, msg, spans) =
+        parse_macro_input!(input as JoinBuilderWithMessage);
 
     if original_path.is_absolute() {
         if original_path.is_dir() {
             output_path(original_path)
         } else {
-            compile_error_not_directory(original_path)
+            compile_error_with_message(
+                msg.as_deref(),
+                spans.error_span(),
+                original_path,
+                compile_error_not_directory,
+            )
         }
     } else {
-        compile_error_not_absolute(original_path)
+        compile_error_with_message(
+            msg.as_deref(),
+            spans.error_span(),
+            original_path,
+            compile_error_not_absolute,
+        )
     }
 }
 
 /// Allows input a absolute path. Checks whether it is not a directory and returns the absolute path.
 ///
+/// A custom error message can be set by the leading `msg = "<message>"` option, where `{}` is replaced with the resolved path.
+///
 /// Multiple components can be input by using commas to separate them.
 #[proc_macro]
 pub fn not_directory_absolute_path(input: TokenStream) -> TokenStream {
-    let original_path: PathBuf = parse_macro_input!(input as JoinBuilder).into();
+    let JoinBuilderWithMessage(original_path, msg, spans) =
+        parse_macro_input!(input as JoinBuilderWithMessage);
 
     if original_path.is_absolute() {
-        if original_path.metadata().map(|m| !m.is_dir()).unwrap_or(false) {
-            output_path(original_path)
-        } else {
-            compile_error_directory(original_path)
+        match original_path.metadata() {
+            Ok(metadata) if metadata.is_dir() => compile_error_with_message(
+                msg.as_deref(),
+                spans.error_span(),
+                original_path,
+                compile_error_directory,
+            ),
+            Ok(_) => output_path(original_path),
+            Err(_) => compile_error_with_message(
+                msg.as_deref(),
+                spans.error_span(),
+                original_path,
+                compile_error_not_exist,
+            ),
         }
     } else {
-        compile_error_not_absolute(original_path)
+        compile_error_with_message(
+            msg.as_deref(),
+            spans.error_span(),
+            original_path,
+            compile_error_not_absolute,
+        )
     }
 }
 
 /// Allows input a absolute path. Checks whether it is a file and returns the absolute path.
 ///
+/// A custom error message can be set by the leading `msg = "<message>"` option, where `{}` is replaced with the resolved path.
+///
 /// Multiple components can be input by using commas to separate them.
 #[proc_macro]
 pub fn file_absolute_path(input: TokenStream) -> TokenStream {
-    let original_path: PathBuf = parse_macro_input!(input as JoinBuilder).into();
+    let JoinBuilderWithMessage(original_path, msg, spans) =
+        parse_macro_input!(input as JoinBuilderWithMessage);
 
     if original_path.is_absolute() {
         if original_path.is_file() {
             output_path(original_path)
         } else {
-            compile_error_not_file(original_path)
+            compile_error_with_message(
+                msg.as_deref(),
+                spans.error_span(),
+                original_path,
+                compile_error_not_file,
+            )
         }
     } else {
-        compile_error_not_absolute(original_path)
+        compile_error_with_message(
+            msg.as_deref(),
+            spans.error_span(),
+            original_path,
+            compile_error_not_absolute,
+        )
     }
 }
 
@@ -346,7 +1032,7 @@ pub fn get_file_name(input: TokenStream) -> TokenStream {
     match jb.0.file_name() {
         Some(file_name) => output_os_str(file_name),
         None => match jb.1 {
-            Some(expr) => output_expr(&expr),
+            Some(default_value) => output_default_os_str(&default_value),
             None => compile_error(format!("The path {:?} has no file name", jb.0)),
         },
     }
@@ -354,15 +1040,29 @@ pub fn get_file_name(input: TokenStream) -> TokenStream {
 
 /// Gets the file stem for other purposes. If there is no file stem, the default value will be used, or a compile error will be shown.
 ///
+/// Only the last file extension is removed unless the leading `strip_all` flag is added, e.g. `get_file_stem!(strip_all, "archive.tar.gz")` returns `"archive"`.
+///
 /// Multiple components can be input by using commas to separate them.
 #[proc_macro]
 pub fn get_file_stem(input: TokenStream) -> TokenStream {
-    let jb = parse_macro_input!(input as JoinBuilderNoBeautifyWithDefaultValue);
+    let jb = parse_macro_input!(input as JoinBuilderNoBeautifyWithStripAllAndDefaultValue);
 
     match jb.0.file_stem() {
-        Some(file_stem) => output_os_str(file_stem),
-        None => match jb.1 {
-            Some(expr) => output_expr(&expr),
+        Some(mut file_stem) => {
+            if jb.1 {
+                while let Some(stem) = Path::new(file_stem).file_stem() {
+                    if stem == file_stem {
+                        break;
+                    }
+
+                    file_stem = stem;
+                }
+            }
+
+            output_os_str(file_stem)
+        },
+        None => match jb.2 {
+            Some(default_value) => output_default_os_str(&default_value),
             None => compile_error(format!("The path {:?} has no file stem", jb.0)),
         },
     }
@@ -378,7 +1078,7 @@ pub fn get_extension(input: TokenStream) -> TokenStream {
     match jb.0.extension() {
         Some(extension) => output_os_str(extension),
         None => match jb.1 {
-            Some(expr) => output_expr(&expr),
+            Some(default_value) => output_default_os_str(&default_value),
             None => compile_error(format!("The path {:?} has no file extension", jb.0)),
         },
     }
@@ -394,27 +1094,27 @@ pub fn get_parent(input: TokenStream) -> TokenStream {
     match jb.0.parent() {
         Some(parent) => output_path(parent),
         None => match jb.1 {
-            Some(expr) => output_expr(&expr),
+            Some(default_value) => output_default_path(&default_value),
             None => compile_error(format!("The path {:?} has no parent", jb.0)),
         },
     }
 }
 
 #[cfg(feature = "mime_guess")]
-/// Guesses the mime type by the path. If the guess fails, the default value will be used, or a compile error will be shown.
+/// Guesses the mime type by the file name. Some well-known file names which have no extensions, such as `Makefile` and `.gitignore`, are recognized by the whole file name, and the others are recognized by the file extension. If the guess fails, the default value will be used, or a compile error will be shown.
+///
+/// ```compile_fail
+/// #[macro_use] extern crate manifest_dir_macros;
+///
+/// println!(mime_guess!("LICENSE"));
+/// ```
 ///
 /// Multiple components can be input by using commas to separate them.
 #[proc_macro]
 pub fn mime_guess(input: TokenStream) -> TokenStream {
     let jb = parse_macro_input!(input as JoinBuilderNoBeautifyWithDefaultValue);
 
-    match jb
-        .0
-        .extension()
-        .and_then(|ext| ext.to_str())
-        .and_then(|ext| mime_guess::from_ext(ext).first())
-        .map(|mime| mime.to_string())
-    {
+    match guess_mimes(&jb.0).first().map(|mime| mime.to_string()) {
         Some(mime) => {
             let code = quote! {
                 #mime
@@ -423,10 +1123,2665 @@ pub fn mime_guess(input: TokenStream) -> TokenStream {
             code.into()
         },
         None => match jb.1 {
-            Some(expr) => output_expr(&expr),
+            Some(default_value) => output_default_os_str(&default_value),
             None => {
                 compile_error(format!("The path {:?} can not be guessed for its mime type", jb.0))
             },
         },
     }
 }
+
+/// Counts the components of a path and returns it as a `usize`. The path is counted as it is input without being resolved against the CARGO_MANIFEST_DIR (a directory where your `Cargo.toml` located), so a relative path is counted as its depth below that directory, and an absolute path is counted as its depth below the root.
+///
+/// `Prefix`, `RootDir` and `CurDir` components are not counted, so `depth!("/usr/lib")`, `depth!("usr/lib")` and `depth!("./usr/lib")` are all `2`, and `depth!(r"C:\Windows\System32")` is `2` on Windows.
+///
+/// A path which contains a `ParentDir` component is rejected, because its depth depends on the layout outside the path.
+///
+/// ```compile_fail
+/// #[macro_use] extern crate manifest_dir_macros;
+///
+/// const _: usize = depth!("../src/lib.rs");
+/// ```
+///
+/// Multiple components can be input by using commas to separate them.
+#[proc_macro]
+pub fn depth(input: TokenStream) -> TokenStream {
+    let original_path: PathBuf = parse_macro_input!(input as JoinBuilder).into();
+
+    if original_path.components().any(|c| c == Component::ParentDir) {
+        return compile_error(format!(
+            "The path {:?} contains a `..` component, so its depth cannot be counted",
+            original_path
+        ));
+    }
+
+    let depth = original_path
+        .components()
+        .filter(|c| !matches!(c, Component::Prefix(_) | Component::RootDir | Component::CurDir))
+        .count();
+
+    output_usize(depth)
+}
+
+/// Allows input an absolute path, or a relative path. If a relative path is input, it will be relative to the CARGO_MANIFEST_DIR (a directory where your `Cargo.toml` located). Returns an absolute path, and the byte length of its file name must be within `min` and `max` (inclusive).
+///
+/// The range is set by the leading `min = <usize>` and `max = <usize>` options. Either of them can be omitted.
+///
+/// A file name which is shorter than `min` or longer than `max` is rejected.
+///
+/// ```compile_fail
+/// #[macro_use] extern crate manifest_dir_macros;
+///
+/// println!(filename_len_range!(min = 11, max = 255, "Cargo.toml"));
+/// ```
+///
+/// ```compile_fail
+/// #[macro_use] extern crate manifest_dir_macros;
+///
+/// println!(filename_len_range!(min = 1, max = 9, "Cargo.toml"));
+/// ```
+///
+/// Multiple components can be input by using commas to separate them.
+#[proc_macro]
+pub fn filename_len_range(input: TokenStream) -> TokenStream {
+    let jb = parse_macro_input!(input as JoinBuilderWithLengthRange);
+
+    let p = if jb.0.is_absolute() { jb.0 } else { MANIFEST_DIR.join(jb.0) };
+
+    match p.file_name() {
+        Some(file_name) => {
+            let len = file_name.len();
+
+            if len >= jb.1 && len <= jb.2 {
+                output_path(p)
+            } else {
+                compile_error(format!(
+                    "The file name of the path {:?} is {} bytes long, which is not in the range \
+                     [{}, {}]",
+                    p, len, jb.1, jb.2
+                ))
+            }
+        },
+        None => compile_error(format!("The path {:?} has no file name", p)),
+    }
+}
+
+/// Allows input an absolute path, or a relative path. If a relative path is input, it will be relative to the CARGO_MANIFEST_DIR (a directory where your `Cargo.toml` located). The path must be an existing directory. Finds the file which was modified last in the directory and returns a `(&str, u64)` tuple of its absolute path and its modification time in seconds since the UNIX epoch.
+///
+/// Add a leading `recursive` flag to search subdirectories as well. The recursion can be limited by the `max_depth = <usize>` option after the flag. Symbolic links to directories are not followed. If several files have the same modification time, the first one in the sorted order is chosen.
+///
+/// Multiple components can be input by using commas to separate them.
+#[proc_macro]
+pub fn newest_in_dir(input: TokenStream) -> TokenStream {
+    let jb = parse_macro_input!(input as JoinBuilderWithRecursive);
+
+    let p = if jb.0.is_absolute() { jb.0 } else { MANIFEST_DIR.join(jb.0) };
+
+    if !p.is_dir() {
+        return compile_error_not_directory(p);
+    }
+
+    let files = match read_dir_files(&p, jb.1) {
+        Ok(files) => files,
+        Err(err) => return compile_error_io(p, err),
+    };
+
+    let mut newest: Option<(PathBuf, u64)> = None;
+
+    for file in files {
+        let mtime = match modified_secs(&file) {
+            Ok(mtime) => mtime,
+            Err(err) => return compile_error_io(file, err),
+        };
+
+        if newest.as_ref().map(|(_, newest_mtime)| mtime > *newest_mtime).unwrap_or(true) {
+            newest = Some((file, mtime));
+        }
+    }
+
+    match newest {
+        Some((file, mtime)) => {
+            let file = match path_to_utf8_str(&file) {
+                Ok(file) => file,
+                Err(err) => return err,
+            };
+
+            let mtime = proc_macro2::Literal::u64_suffixed(mtime);
+
+            let code = quote! {
+                (#file, #mtime)
+            };
+
+            code.into()
+        },
+        None => compile_error(format!("The directory {:?} has no files", p)),
+    }
+}
+
+/// Allows input an absolute path, or a relative path. If a relative path is input, it will be relative to the CARGO_MANIFEST_DIR (a directory where your `Cargo.toml` located). Every `{profile}` in the path is replaced with the build profile (such as `debug` or `release`), and an absolute path is returned.
+///
+/// The build profile is read from the `PROFILE` environment variable when the macro is expanded. Cargo only sets it for running build scripts, so your build script has to pass it to the compiler of your crate as follows. Another environment variable can be chosen by the leading `env = "<NAME>"` option.
+///
+/// ```rust,ignore
+/// // build.rs
+/// fn main() {
+///     println!("cargo:rustc-env=PROFILE={}", std::env::var("PROFILE").unwrap());
+/// }
+/// ```
+///
+/// Multiple components can be input by using commas to separate them.
+#[proc_macro]
+pub fn profile_path(input: TokenStream) -> TokenStream {
+    let jb = parse_macro_input!(input as JoinBuilderWithEnv);
+
+    let profile = match env::var(&jb.1) {
+        Ok(profile) => profile,
+        Err(_) => {
+            return compile_error(format!(
+                "The {} environment variable is not set. Cargo only sets PROFILE for build \
+                 scripts, so add `println!(\"cargo:rustc-env={}={{}}\", \
+                 std::env::var(\"PROFILE\").unwrap());` to your build script",
+                jb.1, jb.1
+            ))
+        },
+    };
+
+    let original_path = match path_to_utf8_str(&jb.0) {
+        Ok(s) => PathBuf::from(s.replace("{profile}", &profile)),
+        Err(err) => return err,
+    };
+
+    let p =
+        if original_path.is_absolute() { original_path } else { MANIFEST_DIR.join(original_path) };
+
+    output_path(p)
+}
+
+#[cfg(feature = "mime_guess")]
+/// Guesses all the possible mime types by the file name in the same way as `mime_guess!` and returns a `&[&str]` slice whose first element is the same as the result of `mime_guess!`. If the guess fails, a slice only containing the default value will be used, or a compile error will be shown.
+///
+/// Multiple components can be input by using commas to separate them.
+#[proc_macro]
+pub fn mime_guess_all(input: TokenStream) -> TokenStream {
+    let jb = parse_macro_input!(input as JoinBuilderNoBeautifyWithDefaultValue);
+
+    let mimes: Vec<String> = guess_mimes(&jb.0).iter().map(|mime| mime.to_string()).collect();
+
+    if mimes.is_empty() {
+        match jb.1 {
+            Some(default_value) => {
+                let code = quote! {
+                    &[#default_value]
+                };
+
+                code.into()
+            },
+            None => {
+                compile_error(format!("The path {:?} can not be guessed for its mime type", jb.0))
+            },
+        }
+    } else {
+        let code = quote! {
+            &[#(#mimes),*]
+        };
+
+        code.into()
+    }
+}
+
+/// Allows input an absolute path, or a relative path. If a relative path is input, it will be relative to the CARGO_MANIFEST_DIR (a directory where your `Cargo.toml` located). Consecutive identical components of the input path are collapsed into one (e.g. `src/src/lib.rs` becomes `src/lib.rs`), and an absolute path is returned.
+///
+/// This is a lexical transformation which does not access the file system, so it may change the meaning of the path if the repeated directory really exists.
+///
+/// Multiple components can be input by using commas to separate them.
+#[proc_macro]
+pub fn collapse_repeats(input: TokenStream) -> TokenStream {
+    let original_path: PathBuf = parse_macro_input!(input as JoinBuilder).into();
+
+    let mut collapsed_path = PathBuf::new();
+    let mut last_component = None;
+
+    for component in original_path.components() {
+        if last_component != Some(component) {
+            collapsed_path.push(component);
+
+            last_component = Some(component);
+        }
+    }
+
+    let p = if collapsed_path.is_absolute() {
+        collapsed_path
+    } else {
+        MANIFEST_DIR.join(collapsed_path)
+    };
+
+    output_path(p)
+}
+
+/// Allows input an absolute path, or a relative path. If a relative path is input, it will be relative to the CARGO_MANIFEST_DIR (a directory where your `Cargo.toml` located). The path must be an existing file. Returns a `bool` which indicates whether the file starts with a UTF-8 or UTF-16 byte-order mark.
+///
+/// Multiple components can be input by using commas to separate them.
+#[proc_macro]
+pub fn has_bom(input: TokenStream) -> TokenStream {
+    let original_path: PathBuf = parse_macro_input!(input as JoinBuilder).into();
+
+    let p =
+        if original_path.is_absolute() { original_path } else { MANIFEST_DIR.join(original_path) };
+
+    if !p.is_file() {
+        return compile_error_not_file(p);
+    }
+
+    match detect_bom(&p) {
+        Ok(kind) => {
+            let has_bom = kind != "none";
+
+            let code = quote! {
+                #has_bom
+            };
+
+            code.into()
+        },
+        Err(err) => compile_error_io(p, err),
+    }
+}
+
+/// Allows input an absolute path, or a relative path. If a relative path is input, it will be relative to the CARGO_MANIFEST_DIR (a directory where your `Cargo.toml` located). The path must be an existing file. Returns the kind of the byte-order mark at the beginning of the file, which is `"utf8"`, `"utf16le"`, `"utf16be"` or `"none"`.
+///
+/// Multiple components can be input by using commas to separate them.
+#[proc_macro]
+pub fn bom_kind(input: TokenStream) -> TokenStream {
+    let original_path: PathBuf = parse_macro_input!(input as JoinBuilder).into();
+
+    let p =
+        if original_path.is_absolute() { original_path } else { MANIFEST_DIR.join(original_path) };
+
+    if !p.is_file() {
+        return compile_error_not_file(p);
+    }
+
+    match detect_bom(&p) {
+        Ok(kind) => {
+            let code = quote! {
+                #kind
+            };
+
+            code.into()
+        },
+        Err(err) => compile_error_io(p, err),
+    }
+}
+
+/// Allows input an absolute path, or a relative path. If a relative path is input, it will be relative to the CARGO_MANIFEST_DIR (a directory where your `Cargo.toml` located). Returns the absolute path of its parent, and the parent must be an existing directory. If not, the default value will be used, or a compile error will be shown.
+///
+/// Multiple components can be input by using commas to separate them.
+#[proc_macro]
+pub fn parent_dir_path(input: TokenStream) -> TokenStream {
+    let jb = parse_macro_input!(input as JoinBuilderWithDefaultValue);
+
+    let p = if jb.0.is_absolute() { jb.0 } else { MANIFEST_DIR.join(jb.0) };
+
+    match p.parent() {
+        Some(parent) if parent.is_dir() => output_path(parent),
+        parent => match jb.1 {
+            Some(default_value) => output_default_path(&default_value),
+            None => match parent {
+                Some(parent) => compile_error_not_directory(parent),
+                None => compile_error(format!("The path {:?} has no parent", p)),
+            },
+        },
+    }
+}
+
+#[cfg(feature = "mime_guess")]
+/// Allows input an absolute path, or a relative path. If a relative path is input, it will be relative to the CARGO_MANIFEST_DIR (a directory where your `Cargo.toml` located). The path must be an existing directory. Returns a `&[(&str, &str)]` slice of the files in the directory, sorted by their paths. Each element is a tuple of the path relative to the directory (joined with `/` regardless of the platform) and its guessed mime type. If the mime type cannot be guessed, `application/octet-stream` will be used.
+///
+/// Add a leading `recursive` flag to include files in subdirectories as well. The recursion can be limited by the `max_depth = <usize>` option after the flag. Symbolic links to directories are not followed.
+///
+/// Multiple components can be input by using commas to separate them.
+#[proc_macro]
+pub fn dir_mime_table(input: TokenStream) -> TokenStream {
+    let jb = parse_macro_input!(input as JoinBuilderWithRecursive);
+
+    let p = if jb.0.is_absolute() { jb.0 } else { MANIFEST_DIR.join(jb.0) };
+
+    if !p.is_dir() {
+        return compile_error_not_directory(p);
+    }
+
+    let files = match read_dir_files(&p, jb.1) {
+        Ok(files) => files,
+        Err(err) => return compile_error_io(p, err),
+    };
+
+    let mut web_paths = Vec::with_capacity(files.len());
+    let mut mimes = Vec::with_capacity(files.len());
+
+    for file in files {
+        let relative_path = file.strip_prefix(&p).unwrap();
+
+        match relative_path_to_slash_string(relative_path) {
+            Ok(web_path) => web_paths.push(web_path),
+            Err(err) => return err,
+        }
+
+        mimes.push(
+            guess_mimes(relative_path)
+                .first()
+                .map(|mime| mime.to_string())
+                .unwrap_or_else(|| String::from("application/octet-stream")),
+        );
+    }
+
+    let code = quote! {
+        &[#((#web_paths, #mimes)),*]
+    };
+
+    code.into()
+}
+
+/// Allows input an absolute path, or a relative path. If a relative path is input, it will be relative to the directory which is `levels` levels up from the CARGO_MANIFEST_DIR (a directory where your `Cargo.toml` located). Returns an absolute path.
+///
+/// The number of levels is set by the leading `levels = <usize>` option, which defaults to `1`. Going up beyond the root causes a compile error.
+///
+/// Multiple components can be input by using commas to separate them.
+#[proc_macro]
+pub fn up_path(input: TokenStream) -> TokenStream {
+    let jb = parse_macro_input!(input as JoinBuilderWithLevels);
+
+    let mut base = MANIFEST_DIR.as_path();
+
+    for _ in 0..jb.1 {
+        base = match base.parent() {
+            Some(parent) => parent,
+            None => {
+                return compile_error(format!(
+                    "Cannot go up {} levels from the path {:?}",
+                    jb.1,
+                    MANIFEST_DIR.as_path()
+                ))
+            },
+        };
+    }
+
+    output_path(base.join(jb.0))
+}
+
+#[cfg(feature = "force-separator")]
+/// Allows input an absolute path, or a relative path. If a relative path is input, it will be relative to the CARGO_MANIFEST_DIR (a directory where your `Cargo.toml` located). Returns an absolute path whose separators are all replaced with the separator set by the leading `sep = '/'` or `sep = '\\'` option, regardless of the platform.
+///
+/// Multiple components can be input by using commas to separate them.
+#[proc_macro]
+pub fn path_with_separator(input: TokenStream) -> TokenStream {
+    let jb = parse_macro_input!(input as JoinBuilderWithSeparator);
+
+    let p = if jb.0.is_absolute() { jb.0 } else { MANIFEST_DIR.join(jb.0) };
+
+    match path_to_utf8_str(&p) {
+        Ok(s) => output_path(replace_separator(s.to_string(), jb.1)),
+        Err(err) => err,
+    }
+}
+
+/// Checks whether the path has the given file extension, which is input after the path. Returns a `bool`. If the path has no file extension, returns `false`.
+///
+/// The comparison is case-sensitive unless the leading `ignore_case` flag is added.
+///
+/// Multiple components can be input by using commas to separate them.
+#[proc_macro]
+pub fn extension_is(input: TokenStream) -> TokenStream {
+    let jb = parse_macro_input!(input as JoinBuilderNoBeautifyWithTrailingValue);
+
+    let expected_extension = OsStr::new(&jb.1);
+
+    let result = match jb.0.extension() {
+        Some(extension) => {
+            if jb.2 {
+                eq_ignore_case(extension, expected_extension)
+            } else {
+                extension == expected_extension
+            }
+        },
+        None => false,
+    };
+
+    let code = quote! {
+        #result
+    };
+
+    code.into()
+}
+
+/// Allows input a relative path. Returns the URL-safe base64 encoding (without padding) of the path string itself, whose components are joined with `/` regardless of the platform. The file contents are not read.
+///
+/// The token can be decoded back to the relative path by any URL-safe base64 decoder which accepts unpadded input.
+///
+/// Multiple components can be input by using commas to separate them.
+#[proc_macro]
+pub fn path_token(input: TokenStream) -> TokenStream {
+    let original_path: PathBuf = parse_macro_input!(input as JoinBuilderNoBeautify).into();
+
+    if original_path.is_relative() {
+        match relative_path_to_slash_string(&original_path) {
+            Ok(s) => {
+                let token = encode_base64_url(s.as_bytes());
+
+                let code = quote! {
+                    #token
+                };
+
+                code.into()
+            },
+            Err(err) => err,
+        }
+    } else {
+        compile_error_not_relative(original_path)
+    }
+}
+
+#[cfg(feature = "jsonschema")]
+/// Allows input an absolute path, or a relative path. If a relative path is input, it will be relative to the CARGO_MANIFEST_DIR (a directory where your `Cargo.toml` located). The path must be an existing JSON file which is valid against the JSON Schema file set by the leading `schema = "<path>"` option. Returns an absolute path.
+///
+/// The schema path is resolved in the same way as the input path. If the validation fails, all the validation errors are shown in a compile error.
+///
+/// ```compile_fail
+/// #[macro_use] extern crate manifest_dir_macros;
+///
+/// println!(validate_schema!(
+///     schema = "tests/fixtures/schema/config.schema.json",
+///     "tests/fixtures/schema/invalid.json"
+/// ));
+/// ```
+///
+/// Multiple components can be input by using commas to separate them.
+#[proc_macro]
+pub fn validate_schema(input: TokenStream) -> TokenStream {
+    let jb = parse_macro_input!(input as JoinBuilderWithSchema);
+
+    let p = if jb.0.is_absolute() { jb.0 } else { MANIFEST_DIR.join(jb.0) };
+    let schema_path = if jb.1.is_absolute() { jb.1 } else { MANIFEST_DIR.join(jb.1) };
+
+    let schema = match read_json_file(&schema_path) {
+        Ok(schema) => schema,
+        Err(err) => return err,
+    };
+
+    let data = match read_json_file(&p) {
+        Ok(data) => data,
+        Err(err) => return err,
+    };
+
+    let validator = match jsonschema::validator_for(&schema) {
+        Ok(validator) => validator,
+        Err(err) => {
+            return compile_error(format!(
+                "The file {:?} is not a valid JSON Schema: {}",
+                schema_path, err
+            ))
+        },
+    };
+
+    let errors: Vec<String> = validator
+        .iter_errors(&data)
+        .map(|err| {
+            let instance_path = err.instance_path().to_string();
+
+            if instance_path.is_empty() {
+                err.to_string()
+            } else {
+                format!("{}: {}", instance_path, err)
+            }
+        })
+        .collect();
+
+    if errors.is_empty() {
+        output_path(p)
+    } else {
+        compile_error(format!(
+            "The file {:?} does not match the schema {:?}:\n{}",
+            p,
+            schema_path,
+            errors.join("\n")
+        ))
+    }
+}
+
+/// Allows input an absolute path, or a relative path. If a relative path is input, it will be relative to the CARGO_MANIFEST_DIR (a directory where your `Cargo.toml` located). Returns the last modification time of the path in seconds since the UNIX epoch as a `u64`. If the time cannot be read, the default value will be used, or a compile error will be shown.
+///
+/// Be aware that this makes the build non-reproducible because the output depends on the file system. A file is tracked by `include_bytes!`, so the macro is re-expanded when the file changes. A directory cannot be tracked, so the macro is not re-expanded when only the directory changes.
+///
+/// Multiple components can be input by using commas to separate them.
+#[proc_macro]
+pub fn modified_time(input: TokenStream) -> TokenStream {
+    let jb = parse_macro_input!(input as JoinBuilderWithDefaultValue);
+
+    let p = if jb.0.is_absolute() { jb.0 } else { MANIFEST_DIR.join(jb.0) };
+
+    match modified_secs(&p) {
+        Ok(mtime) => {
+            if p.is_file() {
+                output_tracked(&p, proc_macro2::Literal::u64_suffixed(mtime))
+            } else {
+                output_u64(mtime)
+            }
+        },
+        Err(err) => match jb.1 {
+            Some(DefaultValue::Expr(expr)) => output_expr(&expr),
+            Some(DefaultValue::LitStr(_)) => compile_error(
+                "The default value of `modified_time!` needs to be a `u64`, not a string",
+            ),
+            None => {
+                if p.exists() {
+                    compile_error_io(p, err)
+                } else {
+                    compile_error_not_exist(p)
+                }
+            },
+        },
+    }
+}
+
+/// Allows input an absolute path, or a relative path. If a relative path is input, it will be relative to the CARGO_MANIFEST_DIR (a directory where your `Cargo.toml` located). Returns the number of components of the absolute path which are symbolic links as a `usize`.
+///
+/// Every ancestor of the path, including the CARGO_MANIFEST_DIR and its ancestors, is checked from the root. The walk stops at the first component which does not exist, so the components after it are not counted.
+///
+/// Multiple components can be input by using commas to separate them.
+#[proc_macro]
+pub fn symlink_component_count(input: TokenStream) -> TokenStream {
+    let original_path: PathBuf = parse_macro_input!(input as JoinBuilder).into();
+
+    let p =
+        if original_path.is_absolute() { original_path } else { MANIFEST_DIR.join(original_path) };
+
+    let mut count = 0usize;
+    let mut current_path = PathBuf::new();
+
+    for component in p.components() {
+        current_path.push(component);
+
+        if let Component::Normal(_) = component {
+            match current_path.symlink_metadata() {
+                Ok(metadata) => {
+                    if metadata.file_type().is_symlink() {
+                        count += 1;
+                    }
+                },
+                Err(_) => break,
+            }
+        }
+    }
+
+    output_usize(count)
+}
+
+#[cfg(feature = "mime_guess")]
+/// Allows input an absolute path, or a relative path. If a relative path is input, it will be relative to the CARGO_MANIFEST_DIR (a directory where your `Cargo.toml` located). The path must be an existing file. Detects the type of the file by its magic number (e.g. PNG, JPEG, GIF, WebP, PDF, ZIP, gzip, WebAssembly, ...) and returns the absolute path appended with the customary file extension of that type, e.g. `archive.tar` becomes `archive.tar.gz`. If the path already ends with that extension (case-insensitively), it is returned as it is. If the type cannot be detected, the default value will be used, or a compile error will be shown.
+///
+/// ```compile_fail
+/// #[macro_use] extern crate manifest_dir_macros;
+///
+/// println!(content_extension!("Cargo.toml"));
+/// ```
+///
+/// Multiple components can be input by using commas to separate them.
+#[proc_macro]
+pub fn content_extension(input: TokenStream) -> TokenStream {
+    let jb = parse_macro_input!(input as JoinBuilderWithDefaultValue);
+
+    let mut p = if jb.0.is_absolute() { jb.0 } else { MANIFEST_DIR.join(jb.0) };
+
+    if !p.is_file() {
+        return compile_error_not_file(p);
+    }
+
+    match detect_extension_by_magic(&p) {
+        Ok(Some(extension)) => {
+            let has_extension =
+                p.extension().map(|e| eq_ignore_case(e, OsStr::new(extension))).unwrap_or(false);
+
+            if !has_extension {
+                let mut file_name = p.file_name().unwrap().to_os_string();
+
+                file_name.push(".");
+                file_name.push(extension);
+
+                p.set_file_name(file_name);
+            }
+
+            output_path(p)
+        },
+        Ok(None) => match jb.1 {
+            Some(default_value) => output_default_path(&default_value),
+            None => compile_error(format!("The type of the file {:?} cannot be detected", p)),
+        },
+        Err(err) => compile_error_io(p, err),
+    }
+}
+
+#[cfg(feature = "hash")]
+/// Allows input an absolute path, or a relative path. If a relative path is input, it will be relative to the CARGO_MANIFEST_DIR (a directory where your `Cargo.toml` located). The path must be an existing file. Returns the lowercase hex digest of the file contents as a `&'static str`.
+///
+/// SHA-256 is used by default. The algorithm can be chosen by the leading `algo = "sha256"` or `algo = "sha1"` option.
+///
+/// The output is a block expression which also includes the file by `include_bytes!`, so that the code is recompiled when the file changes.
+///
+/// Multiple components can be input by using commas to separate them.
+#[proc_macro]
+pub fn content_hash(input: TokenStream) -> TokenStream {
+    let jb = parse_macro_input!(input as JoinBuilderWithHashAlgorithm);
+
+    let p = if jb.0.is_absolute() { jb.0 } else { MANIFEST_DIR.join(jb.0) };
+
+    if !p.is_file() {
+        return compile_error_not_file(p);
+    }
+
+    match std::fs::read(&p) {
+        Ok(data) => output_tracked(&p, hash_bytes(&data, jb.1)),
+        Err(err) => compile_error_io(p, err),
+    }
+}
+
+/// Allows input a relative path, which is relative to the CARGO_MANIFEST_DIR (a directory where your `Cargo.toml` located), or an absolute path under the CARGO_MANIFEST_DIR. Returns a `&[&str]` slice of the names of the directories leading to the path from the CARGO_MANIFEST_DIR. The last component (the file itself) is not included.
+///
+/// An absolute path which is not under the CARGO_MANIFEST_DIR causes a compile error.
+///
+/// Multiple components can be input by using commas to separate them.
+#[proc_macro]
+pub fn breadcrumbs(input: TokenStream) -> TokenStream {
+    let original_path: PathBuf = parse_macro_input!(input as JoinBuilder).into();
+
+    let relative_path = if original_path.is_absolute() {
+        match original_path.strip_prefix(MANIFEST_DIR.as_path()) {
+            Ok(relative_path) => relative_path.to_path_buf(),
+            Err(_) => {
+                return compile_error(format!(
+                    "The path {:?} is not under the CARGO_MANIFEST_DIR",
+                    original_path
+                ))
+            },
+        }
+    } else {
+        original_path
+    };
+
+    let mut names = Vec::new();
+
+    if let Some(parent) = relative_path.parent() {
+        for component in parent.components() {
+            if component != Component::CurDir {
+                match path_to_utf8_str(Path::new(component.as_os_str())) {
+                    Ok(name) => names.push(name),
+                    Err(err) => return err,
+                }
+            }
+        }
+    }
+
+    let code = quote! {
+        &[#(#names),*] as &[&str]
+    };
+
+    code.into()
+}
+
+/// Allows input an absolute path, or a relative path. If a relative path is input, it will be relative to the CARGO_MANIFEST_DIR (a directory where your `Cargo.toml` located). Returns `Some` of the absolute path as an `Option<&'static str>` if it exists, or `None` if it does not.
+///
+/// Multiple components can be input by using commas to separate them.
+#[proc_macro]
+pub fn try_exist_path(input: TokenStream) -> TokenStream {
+    let original_path: PathBuf = parse_macro_input!(input as JoinBuilder).into();
+
+    let p =
+        if original_path.is_absolute() { original_path } else { MANIFEST_DIR.join(original_path) };
+
+    let code = if p.exists() {
+        match path_to_utf8_str(&p) {
+            Ok(s) => quote! {
+                ::core::option::Option::Some(#s)
+            },
+            Err(err) => return err,
+        }
+    } else {
+        quote! {
+            ::core::option::Option::<&'static str>::None
+        }
+    };
+
+    code.into()
+}
+
+/// Allows input an absolute path, or a relative path. If a relative path is input, it will be relative to the CARGO_MANIFEST_DIR (a directory where your `Cargo.toml` located). The path must exist. Returns an absolute path, and if it is a directory, the file name of the index file is appended to it like what a static file server does.
+///
+/// The file name of the index file is set by the leading `index = "<file name>"` option, which defaults to `index.html`.
+///
+/// Multiple components can be input by using commas to separate them.
+#[proc_macro]
+pub fn index_path(input: TokenStream) -> TokenStream {
+    let jb = parse_macro_input!(input as JoinBuilderWithIndex);
+
+    let p = if jb.0.is_absolute() { jb.0 } else { MANIFEST_DIR.join(jb.0) };
+
+    if p.is_dir() {
+        output_path(p.join(jb.1))
+    } else if p.exists() {
+        output_path(p)
+    } else {
+        compile_error_not_exist(p)
+    }
+}
+
+/// Allows input an absolute path, or a relative path. If a relative path is input, it will be relative to the CARGO_MANIFEST_DIR (a directory where your `Cargo.toml` located). The path must be an existing directory. Returns the number of files in the directory and its subdirectories as a `usize`. Directories are not counted.
+///
+/// The recursion can be limited by the leading `max_depth = <usize>` option. The directory itself is at depth `0`, and the files directly in it are at depth `1`. Symbolic links to directories are not followed.
+///
+/// Multiple components can be input by using commas to separate them.
+#[proc_macro]
+pub fn tree_file_count(input: TokenStream) -> TokenStream {
+    let jb = parse_macro_input!(input as JoinBuilderWithMaxDepth);
+
+    let p = if jb.0.is_absolute() { jb.0 } else { MANIFEST_DIR.join(jb.0) };
+
+    if !p.is_dir() {
+        return compile_error_not_directory(p);
+    }
+
+    match read_dir_files(&p, jb.1) {
+        Ok(files) => output_usize(files.len()),
+        Err(err) => compile_error_io(p, err),
+    }
+}
+
+/// Allows input multiple absolute paths or relative paths. If a relative path is input, it will be relative to the CARGO_MANIFEST_DIR (a directory where your `Cargo.toml` located). Returns the first absolute path which exists. If none of them exists, the default value will be used, or a compile error will be shown.
+///
+/// Multiple paths can be input by using commas to separate them. Multiple components of a path can be grouped by parentheses, e.g. `first_existing_path!(("config", "local.toml"), "config.toml")`.
+#[proc_macro]
+pub fn first_existing_path(input: TokenStream) -> TokenStream {
+    let jb = parse_macro_input!(input as JoinBuilderListWithDefaultValue);
+
+    let mut candidates = Vec::with_capacity(jb.0.len());
+
+    for original_path in jb.0 {
+        let p = if original_path.is_absolute() {
+            original_path
+        } else {
+            MANIFEST_DIR.join(original_path)
+        };
+
+        if p.exists() {
+            return output_path(p);
+        }
+
+        candidates.push(p);
+    }
+
+    match jb.1 {
+        Some(default_value) => output_default_path(&default_value),
+        None => compile_error(format!("None of the paths {:?} exists", candidates)),
+    }
+}
+
+/// Allows input an absolute path under the OUT_DIR, or a relative path. If a relative path is input, it will be relative to the OUT_DIR (a directory where the build script of your crate outputs files). Returns the path relative to the OUT_DIR, which is useful for `include!`-like macros which are already anchored at the OUT_DIR. The `.` and `..` components are resolved lexically, and a path which goes out of the OUT_DIR is rejected.
+///
+/// The OUT_DIR environment variable is only set by Cargo when your crate has a build script.
+///
+/// ```compile_fail
+/// #[macro_use] extern crate manifest_dir_macros;
+///
+/// // This crate has no build script.
+/// println!(out_dir_relative!("generated/mod.rs"));
+/// ```
+///
+/// Multiple components can be input by using commas to separate them.
+#[proc_macro]
+pub fn out_dir_relative(input: TokenStream) -> TokenStream {
+    let original_path: PathBuf = parse_macro_input!(input as JoinBuilder).into();
+
+    let out_dir = match env::var_os("OUT_DIR") {
+        Some(out_dir) => PathBuf::from(out_dir),
+        None => {
+            return compile_error(
+                "The OUT_DIR environment variable is not set. It is only available when your \
+                 crate has a build script",
+            )
+        },
+    };
+
+    let out_dir = normalize_path(&out_dir);
+
+    let p = normalize_path(&out_dir.join(original_path));
+
+    match p.strip_prefix(&out_dir) {
+        Ok(relative_path) if relative_path.as_os_str().is_empty() => output_path("."),
+        Ok(relative_path) => output_path(relative_path),
+        Err(_) => compile_error(format!("The path {:?} is not under the OUT_DIR {:?}", p, out_dir)),
+    }
+}
+
+/// Allows input an absolute path, or a relative path. If a relative path is input, it will be relative to the CARGO_MANIFEST_DIR (a directory where your `Cargo.toml` located). Returns a `bool` which indicates whether the path is a directory with no entries. If the path is not a directory, returns `false`, or shows a compile error if the leading `strict` flag is added.
+///
+/// Multiple components can be input by using commas to separate them.
+#[proc_macro]
+pub fn is_empty_dir(input: TokenStream) -> TokenStream {
+    let jb = parse_macro_input!(input as JoinBuilderWithStrict);
+
+    let p = if jb.0.is_absolute() { jb.0 } else { MANIFEST_DIR.join(jb.0) };
+
+    let is_empty = if p.is_dir() {
+        match p.read_dir() {
+            Ok(mut entries) => entries.next().is_none(),
+            Err(err) => return compile_error_io(p, err),
+        }
+    } else if jb.1 {
+        return compile_error_not_directory(p);
+    } else {
+        false
+    };
+
+    let code = quote! {
+        #is_empty
+    };
+
+    code.into()
+}
+
+#[cfg(feature = "hash")]
+/// Allows input a relative path. It will be relative to the CARGO_MANIFEST_DIR (a directory where your `Cargo.toml` located), and it must be an existing file. Returns the path joined with `/` regardless of the platform and appended with a `?v=<hash>` query for cache busting, where `<hash>` is the first 8 hex digits of the SHA-256 digest of the file contents.
+///
+/// A mount point can be prepended by the leading `prefix = "<prefix>"` option, e.g. `cachebust_url!(prefix = "/static", "css/app.css")` returns something like `/static/css/app.css?v=1a2b3c4d`.
+///
+/// The output is a block expression which also includes the file by `include_bytes!`, so that the code is recompiled when the file changes.
+///
+/// Multiple components can be input by using commas to separate them.
+#[proc_macro]
+pub fn cachebust_url(input: TokenStream) -> TokenStream {
+    let jb = parse_macro_input!(input as JoinBuilderWithPrefix);
+
+    if !jb.0.is_relative() {
+        return compile_error_not_relative(jb.0);
+    }
+
+    let p = MANIFEST_DIR.join(&jb.0);
+
+    if !p.is_file() {
+        return compile_error_not_file(p);
+    }
+
+    let web_path = match relative_path_to_slash_string(&jb.0) {
+        Ok(web_path) => web_path,
+        Err(err) => return err,
+    };
+
+    match std::fs::read(&p) {
+        Ok(data) => {
+            let hash = hash_bytes(&data, HashAlgorithm::Sha256);
+
+            let url = format!(
+                "{}?v={}",
+                join_url_prefix(jb.1.as_deref().unwrap_or_default(), &web_path),
+                &hash[..8]
+            );
+
+            output_tracked(&p, url)
+        },
+        Err(err) => compile_error_io(p, err),
+    }
+}
+
+/// Gets the file stem of the path appended with the given file extension, which is input after the path. Only the file name is returned, without the parent directory. If there is no file stem, the default value will be used, or a compile error will be shown.
+///
+/// For example, `file_name_with_extension!("assets/logo.svg", "png")` returns `"logo.png"`. A leading dot of the extension is ignored, and an empty extension leaves the file stem as it is.
+///
+/// Multiple components can be input by using commas to separate them.
+#[proc_macro]
+pub fn file_name_with_extension(input: TokenStream) -> TokenStream {
+    let jb = parse_macro_input!(input as JoinBuilderNoBeautifyWithTrailingValueAndDefaultValue);
+
+    match jb.0.file_stem() {
+        Some(file_stem) => {
+            let extension = jb.1.strip_prefix('.').unwrap_or(&jb.1);
+
+            let mut file_name = file_stem.to_os_string();
+
+            if !extension.is_empty() {
+                file_name.push(".");
+                file_name.push(extension);
+            }
+
+            output_os_str(file_name)
+        },
+        None => match jb.2 {
+            Some(default_value) => output_default_os_str(&default_value),
+            None => compile_error(format!("The path {:?} has no file stem", jb.0)),
+        },
+    }
+}
+
+/// Allows input an absolute path, or a relative path. If a relative path is input, it will be relative to the CARGO_MANIFEST_DIR (a directory where your `Cargo.toml` located). Checks whether the file extension of the path is in the denylist given by the leading `deny = ["<extension>", ...]` option. Returns a `bool`. If the path has no file extension, returns `false`.
+///
+/// The comparison is case-insensitive.
+///
+/// Multiple components can be input by using commas to separate them.
+#[proc_macro]
+pub fn extension_denied(input: TokenStream) -> TokenStream {
+    let jb = parse_macro_input!(input as JoinBuilderWithDenylist);
+
+    let p = if jb.0.is_absolute() { jb.0 } else { MANIFEST_DIR.join(jb.0) };
+
+    let result = find_denied_extension(&p, &jb.1).is_some();
+
+    let code = quote! {
+        #result
+    };
+
+    code.into()
+}
+
+/// Allows input an absolute path, or a relative path. If a relative path is input, it will be relative to the CARGO_MANIFEST_DIR (a directory where your `Cargo.toml` located). Returns an absolute path, and its file extension must not be in the denylist given by the leading `deny = ["<extension>", ...]` option.
+///
+/// The comparison is case-insensitive.
+///
+/// Multiple components can be input by using commas to separate them.
+#[proc_macro]
+pub fn assert_not_denied(input: TokenStream) -> TokenStream {
+    let jb = parse_macro_input!(input as JoinBuilderWithDenylist);
+
+    let p = if jb.0.is_absolute() { jb.0 } else { MANIFEST_DIR.join(jb.0) };
+
+    match find_denied_extension(&p, &jb.1) {
+        Some(extension) => {
+            compile_error(format!("The path {:?} has a denied file extension {:?}", p, extension))
+        },
+        None => output_path(p),
+    }
+}
+
+/// Allows input an absolute path, or a relative path. If a relative path is input, it will be relative to the CARGO_MANIFEST_DIR (a directory where your `Cargo.toml` located). Returns an absolute path whose file extension is lowercased. The casing of the file stem and the directories is kept.
+///
+/// Multiple components can be input by using commas to separate them.
+#[proc_macro]
+pub fn lower_ext_path(input: TokenStream) -> TokenStream {
+    let original_path: PathBuf = parse_macro_input!(input as JoinBuilder).into();
+
+    let p =
+        if original_path.is_absolute() { original_path } else { MANIFEST_DIR.join(original_path) };
+
+    match p.extension().map(|extension| extension.to_str()) {
+        Some(Some(extension)) => {
+            let extension = extension.to_lowercase();
+
+            output_path(p.with_extension(extension))
+        },
+        Some(None) => compile_error(format!(
+            "The file extension of the path {:?} cannot be canonicalized to a UTF-8 string.",
+            p
+        )),
+        None => output_path(p),
+    }
+}
+
+/// Allows input an absolute path, or a relative path. If a relative path is input, it will be relative to the CARGO_MANIFEST_DIR (a directory where your `Cargo.toml` located). Reads the file, which must be a UTF-8 text file, and returns its contents as a `&str` with the leading and trailing whitespaces (including newlines) removed.
+///
+/// The output is a block expression which also includes the file by `include_bytes!`, so that the code is recompiled when the file changes.
+///
+/// Multiple components can be input by using commas to separate them.
+#[proc_macro]
+pub fn include_trimmed(input: TokenStream) -> TokenStream {
+    let original_path: PathBuf = parse_macro_input!(input as JoinBuilder).into();
+
+    let p =
+        if original_path.is_absolute() { original_path } else { MANIFEST_DIR.join(original_path) };
+
+    if !p.is_file() {
+        return compile_error_not_file(p);
+    }
+
+    match std::fs::read_to_string(&p) {
+        Ok(content) => output_tracked(&p, content.trim()),
+        Err(err) => compile_error_io(p, err),
+    }
+}
+
+/// Allows input an absolute path, or a relative path. If a relative path is input, it will be relative to the CARGO_MANIFEST_DIR (a directory where your `Cargo.toml` located). Returns an absolute path in the extended-length form prefixed with `\\?\` on Windows, such as `\\?\C:\foo` or `\\?\UNC\server\share\foo`.
+///
+/// The `.` and `..` components are resolved lexically and the path is not required to exist. On other platforms, the plain absolute path is returned like `path!`.
+///
+/// Multiple components can be input by using commas to separate them.
+#[proc_macro]
+pub fn verbatim_path(input: TokenStream) -> TokenStream {
+    let original_path: PathBuf = parse_macro_input!(input as JoinBuilder).into();
+
+    let p =
+        if original_path.is_absolute() { original_path } else { MANIFEST_DIR.join(original_path) };
+
+    #[cfg(windows)]
+    let p = to_verbatim_path(&p);
+
+    output_path(p)
+}
+
+/// Allows input an absolute path, or a relative path. If a relative path is input, it will be relative to the CARGO_MANIFEST_DIR (a directory where your `Cargo.toml` located). The path must exist. Returns a `(&str, &str)` tuple of the absolute path and the target of the symbolic link. A relative target is joined to the directory which contains the link.
+///
+/// If the path is not a symbolic link, both of the elements are the absolute path, or a compile error is shown if the leading `strict` flag is added.
+///
+/// Multiple components can be input by using commas to separate them.
+#[proc_macro]
+pub fn link_pair(input: TokenStream) -> TokenStream {
+    let jb = parse_macro_input!(input as JoinBuilderWithStrict);
+
+    let p = if jb.0.is_absolute() { jb.0 } else { MANIFEST_DIR.join(jb.0) };
+
+    let metadata = match p.symlink_metadata() {
+        Ok(metadata) => metadata,
+        Err(_) => return compile_error_not_exist(p),
+    };
+
+    let target = if metadata.file_type().is_symlink() {
+        match p.read_link() {
+            Ok(target) => match p.parent() {
+                Some(parent) => parent.join(target),
+                None => target,
+            },
+            Err(err) => return compile_error_io(p, err),
+        }
+    } else if jb.1 {
+        return compile_error(format!("The path {:?} is not a symbolic link", p));
+    } else {
+        p.clone()
+    };
+
+    let p = match path_to_utf8_str(&p) {
+        Ok(p) => p,
+        Err(err) => return err,
+    };
+
+    let target = match path_to_utf8_str(&target) {
+        Ok(target) => target,
+        Err(err) => return err,
+    };
+
+    let code = quote! {
+        (#p, #target)
+    };
+
+    code.into()
+}
+
+/// Allows input an absolute path, or a relative path. If a relative path is input, it will be relative to the CARGO_MANIFEST_DIR (a directory where your `Cargo.toml` located). Returns the lexically normalized absolute path, and it must be the CARGO_MANIFEST_DIR or be under it after the `.` and `..` components are collapsed.
+///
+/// The path is not required to exist and symbolic links are not resolved.
+///
+/// Multiple components can be input by using commas to separate them.
+#[proc_macro]
+pub fn assert_under_manifest(input: TokenStream) -> TokenStream {
+    let original_path: PathBuf = parse_macro_input!(input as JoinBuilder).into();
+
+    let p =
+        if original_path.is_absolute() { original_path } else { MANIFEST_DIR.join(original_path) };
+
+    let p = normalize_path(&p);
+
+    if p.starts_with(&*MANIFEST_DIR) {
+        output_path(p)
+    } else {
+        compile_error(format!(
+            "The path {:?} is not under the CARGO_MANIFEST_DIR {:?}",
+            p, *MANIFEST_DIR
+        ))
+    }
+}
+
+/// Allows input a relative path. It will be relative to the nearest `.cargo` directory, which is searched from the CARGO_MANIFEST_DIR (a directory where your `Cargo.toml` located) up to the root. Returns an absolute path.
+///
+/// If the leading `relative` flag is added, the returned path is relative to the CARGO_MANIFEST_DIR instead, such as `../.cargo/config.toml`.
+///
+/// Multiple components can be input by using commas to separate them.
+#[proc_macro]
+pub fn cargo_config_relative(input: TokenStream) -> TokenStream {
+    let jb = parse_macro_input!(input as JoinBuilderWithRelative);
+
+    if !jb.0.is_relative() {
+        return compile_error_not_relative(jb.0);
+    }
+
+    let cargo_dir =
+        match MANIFEST_DIR.ancestors().map(|dir| dir.join(".cargo")).find(|dir| dir.is_dir()) {
+            Some(cargo_dir) => cargo_dir,
+            None => {
+                return compile_error(format!(
+                    "No `.cargo` directory is found from the CARGO_MANIFEST_DIR {:?} up to the \
+                     root",
+                    *MANIFEST_DIR
+                ))
+            },
+        };
+
+    let p = cargo_dir.join(jb.0);
+
+    if jb.1 {
+        output_path(relative_path_between(&MANIFEST_DIR, &p))
+    } else {
+        output_path(p)
+    }
+}
+
+/// Allows input a directory and a file path which is relative to the directory. The directory can be an absolute path, or a relative path. If a relative path is input, it will be relative to the CARGO_MANIFEST_DIR (a directory where your `Cargo.toml` located). Returns the index of the file among all the files in the directory and its subdirectories sorted by their paths, as a `usize`.
+///
+/// The index stays the same as long as the set of files which are sorted before the file does not change, so it can be used as a stable numeric ID.
+///
+/// Multiple components of the directory or the file path can be grouped by parentheses, e.g. `dir_index!("assets", ("css", "app.css"))`.
+#[proc_macro]
+pub fn dir_index(input: TokenStream) -> TokenStream {
+    let jb = parse_macro_input!(input as JoinBuilderPair);
+
+    let p = if jb.0.is_absolute() { jb.0 } else { MANIFEST_DIR.join(jb.0) };
+
+    if !p.is_dir() {
+        return compile_error_not_directory(p);
+    }
+
+    if !jb.1.is_relative() {
+        return compile_error_not_relative(jb.1);
+    }
+
+    let file = p.join(jb.1);
+
+    match read_dir_files(&p, None) {
+        Ok(files) => match files.iter().position(|f| *f == file) {
+            Some(index) => output_usize(index),
+            None => {
+                compile_error(format!("The file {:?} is not found in the directory {:?}", file, p))
+            },
+        },
+        Err(err) => compile_error_io(p, err),
+    }
+}
+
+/// The same as `path!`, but returns a `&'static Path` instead of a `&str`.
+///
+/// Multiple components can be input by using commas to separate them.
+#[proc_macro]
+pub fn static_path(input: TokenStream) -> TokenStream {
+    let jb = parse_macro_input!(input as JoinBuilder);
+
+    let p = if jb.0.is_absolute() { jb.0 } else { MANIFEST_DIR.join(jb.0) };
+
+    if jb.1 && !p.is_dir() {
+        compile_error_not_directory(p)
+    } else {
+        output_static_path(p)
+    }
+}
+
+/// The same as `exist_path!`, but returns a `&'static Path` instead of a `&str`.
+///
+/// Multiple components can be input by using commas to separate them.
+#[proc_macro]
+pub fn exist_static_path(input: TokenStream) -> TokenStream {
+    let JoinBuilderWithMessage(original_path, msg, spans) =
+        parse_macro_input!(input as JoinBuilderWithMessage);
+
+    let p =
+        if original_path.is_absolute() { original_path } else { MANIFEST_DIR.join(original_path) };
+
+    if p.exists() {
+        output_static_path(p)
+    } else {
+        compile_error_with_message(msg.as_deref(), spans.error_span(), p, compile_error_not_exist)
+    }
+}
+
+/// The same as `directory_path!`, but returns a `&'static Path` instead of a `&str`.
+///
+/// Multiple components can be input by using commas to separate them.
+#[proc_macro]
+pub fn directory_static_path(input: TokenStream) -> TokenStream {
+    let JoinBuilderWithMessage(original_path, msg, spans) =
+        parse_macro_input!(input as JoinBuilderWithMessage);
+
+    let p =
+        if original_path.is_absolute() { original_path } else { MANIFEST_DIR.join(original_path) };
+
+    if p.is_dir() {
+        output_static_path(p)
+    } else {
+        compile_error_with_message(
+            msg.as_deref(),
+            spans.error_span(),
+            p,
+            compile_error_not_directory,
+        )
+    }
+}
+
+/// The same as `not_directory_path!`, but returns a `&'static Path` instead of a `&str`.
+///
+/// Multiple components can be input by using commas to separate them.
+#[proc_macro]
+pub fn not_directory_static_path(input: TokenStream) -> TokenStream {
+    let JoinBuilderWithMessage(original_path, msg, spans) =
+        parse_macro_input!(input as JoinBuilderWithMessage);
+
+    let p =
+        if original_path.is_absolute() { original_path } else { MANIFEST_DIR.join(original_path) };
+
+    match p.metadata() {
+        Ok(metadata) if metadata.is_dir() => compile_error_with_message(
+            msg.as_deref(),
+            spans.error_span(),
+            p,
+            compile_error_directory,
+        ),
+        Ok(_) => output_static_path(p),
+        Err(_) => compile_error_with_message(
+            msg.as_deref(),
+            spans.error_span(),
+            p,
+            compile_error_not_exist,
+        ),
+    }
+}
+
+/// The same as `file_path!`, but returns a `&'static Path` instead of a `&str`.
+///
+/// Multiple components can be input by using commas to separate them.
+#[proc_macro]
+pub fn file_static_path(input: TokenStream) -> TokenStream {
+    let JoinBuilderWithMessage(original_path, msg, spans) =
+        parse_macro_input!(input as JoinBuilderWithMessage);
+
+    let p =
+        if original_path.is_absolute() { original_path } else { MANIFEST_DIR.join(original_path) };
+
+    if p.is_file() {
+        output_static_path(p)
+    } else {
+        compile_error_with_message(msg.as_deref(), spans.error_span(), p, compile_error_not_file)
+    }
+}
+
+/// Allows input an absolute path, or a relative path. If a relative path is input, it will be relative to the CARGO_MANIFEST_DIR (a directory where your `Cargo.toml` located). The path must be a directory, and a compile error will be shown if two entries in it have names which differ only in case, because they would collide on a case-insensitive file system. Returns `()`.
+///
+/// Only the entries directly in the directory are checked unless the leading `recursive` flag is added. The recursion can be limited by the `max_depth = <usize>` option after the flag.
+///
+/// Multiple components can be input by using commas to separate them.
+#[proc_macro]
+pub fn assert_no_case_collisions(input: TokenStream) -> TokenStream {
+    let jb = parse_macro_input!(input as JoinBuilderWithRecursive);
+
+    let p = if jb.0.is_absolute() { jb.0 } else { MANIFEST_DIR.join(jb.0) };
+
+    if !p.is_dir() {
+        return compile_error_not_directory(p);
+    }
+
+    match find_case_collision(&p, jb.1) {
+        Ok(Some((a, b))) => {
+            compile_error(format!("The paths {:?} and {:?} differ only in case", a, b))
+        },
+        Ok(None) => {
+            let code = quote! {
+                ()
+            };
+
+            code.into()
+        },
+        Err(err) => compile_error_io(p, err),
+    }
+}
+
+/// Joins the components with the path separator of the platform without resolving the path. Returns a single string literal token, not a block or any other expression, so it can be nested in `concat!`, e.g. `concat!(path_concat!("a", "b"), ".txt")`.
+///
+/// Multiple components can be input by using commas to separate them.
+#[proc_macro]
+pub fn path_concat(input: TokenStream) -> TokenStream {
+    let original_path: PathBuf = parse_macro_input!(input as JoinBuilder).into();
+
+    output_path(original_path)
+}
+
+/// Allows input an absolute path, or a relative path. If a relative path is input, it will be relative to the CARGO_MANIFEST_DIR (a directory where your `Cargo.toml` located). Returns an absolute path whose separators are always `/`, even on Windows, so it is suitable for logs and generated documents.
+///
+/// Multiple components can be input by using commas to separate them.
+#[proc_macro]
+pub fn display_path(input: TokenStream) -> TokenStream {
+    let jb = parse_macro_input!(input as JoinBuilder);
+
+    let p = if jb.0.is_absolute() { jb.0 } else { MANIFEST_DIR.join(jb.0) };
+
+    if jb.1 && !p.is_dir() {
+        return compile_error_not_directory(p);
+    }
+
+    match path_to_utf8_str(&p) {
+        Ok(s) => {
+            let s = slash_path(s);
+
+            let code = quote! {
+                #s
+            };
+
+            code.into()
+        },
+        Err(err) => err,
+    }
+}
+
+/// Allows input a relative path as a marker, such as `.git` or `package.json`. Returns the absolute path of the nearest directory which contains the marker, searched from the CARGO_MANIFEST_DIR (a directory where your `Cargo.toml` located) up to the root. If none of them contains the marker, the default value will be used, or a compile error will be shown.
+///
+/// Multiple components can be input by using commas to separate them.
+#[proc_macro]
+pub fn nearest_ancestor_containing(input: TokenStream) -> TokenStream {
+    let jb = parse_macro_input!(input as JoinBuilderWithDefaultValue);
+
+    if !jb.0.is_relative() {
+        return compile_error_not_relative(jb.0);
+    }
+
+    match MANIFEST_DIR.ancestors().find(|dir| dir.join(&jb.0).exists()) {
+        Some(dir) => output_path(dir),
+        None => match jb.1 {
+            Some(default_value) => output_default_path(&default_value),
+            None => compile_error(format!(
+                "No directory containing {:?} is found from the CARGO_MANIFEST_DIR {:?} up to the \
+                 root",
+                jb.0, *MANIFEST_DIR
+            )),
+        },
+    }
+}
+
+/// Gets the file names of multiple paths and returns a `&[&str]` slice. If a path has no file name, the default value will be used, or a compile error will be shown.
+///
+/// Multiple paths can be input by using commas to separate them. Multiple components of a path can be grouped by parentheses, e.g. `file_names!("Cargo.toml", ("src", "lib.rs"))`.
+#[proc_macro]
+pub fn file_names(input: TokenStream) -> TokenStream {
+    let jb = parse_macro_input!(input as JoinBuilderListWithDefaultValue);
+
+    let mut file_names = Vec::with_capacity(jb.0.len());
+
+    for p in jb.0.iter() {
+        match p.file_name() {
+            Some(file_name) => match path_to_utf8_str(Path::new(file_name)) {
+                Ok(file_name) => file_names.push(quote! { #file_name }),
+                Err(err) => return err,
+            },
+            None => match jb.1.as_ref() {
+                Some(default_value) => file_names.push(quote! { #default_value }),
+                None => return compile_error(format!("The path {:?} has no file name", p)),
+            },
+        }
+    }
+
+    let code = quote! {
+        &[#(#file_names),*] as &[&str]
+    };
+
+    code.into()
+}
+
+/// Allows input a base directory and a target path. Each of them can be an absolute path, or a relative path. If a relative path is input, it will be relative to the CARGO_MANIFEST_DIR (a directory where your `Cargo.toml` located). Returns the target path relative to the base directory, e.g. `relative_path_from!("src/bin", "Cargo.toml")` returns `"../../Cargo.toml"`.
+///
+/// Both paths are normalized lexically and are not required to exist. If they are the same, `"."` is returned.
+///
+/// Multiple components of the base directory or the target path can be grouped by parentheses, e.g. `relative_path_from!(("src", "bin"), "Cargo.toml")`.
+#[proc_macro]
+pub fn relative_path_from(input: TokenStream) -> TokenStream {
+    let jb = parse_macro_input!(input as JoinBuilderPair);
+
+    let base = if jb.0.is_absolute() { jb.0 } else { MANIFEST_DIR.join(jb.0) };
+    let p = if jb.1.is_absolute() { jb.1 } else { MANIFEST_DIR.join(jb.1) };
+
+    output_path(relative_path_between(&normalize_path(&base), &normalize_path(&p)))
+}
+
+/// Allows input an absolute path, or a relative path. If a relative path is input, it will be relative to the CARGO_MANIFEST_DIR (a directory where your `Cargo.toml` located). Reads the file, which must be a UTF-8 text file, and returns its contents as a `&str`.
+///
+/// The contents are returned verbatim unless the leading `strip_bom` flag is added, which removes the UTF-8 BOM (`\u{FEFF}`) at the beginning of the file if there is one.
+///
+/// The output is a block expression which also includes the file by `include_bytes!`, so that the code is recompiled when the file changes.
+///
+/// Multiple components can be input by using commas to separate them.
+#[proc_macro]
+pub fn read_to_string(input: TokenStream) -> TokenStream {
+    let jb = parse_macro_input!(input as JoinBuilderWithStripBom);
+
+    let p = if jb.0.is_absolute() { jb.0 } else { MANIFEST_DIR.join(jb.0) };
+
+    if !p.is_file() {
+        return compile_error_not_file(p);
+    }
+
+    match std::fs::read_to_string(&p) {
+        Ok(content) => {
+            let content =
+                if jb.1 { content.strip_prefix('\u{FEFF}').unwrap_or(&content) } else { &content };
+
+            output_tracked(&p, content)
+        },
+        Err(err) => compile_error_io(p, err),
+    }
+}
+
+/// Returns the absolute path of the CARGO_MANIFEST_DIR (a directory where your `Cargo.toml` located). No argument is accepted.
+#[proc_macro]
+pub fn manifest_dir(input: TokenStream) -> TokenStream {
+    if !input.is_empty() {
+        return compile_error("`manifest_dir!` does not accept any argument, use `path!` instead");
+    }
+
+    output_path(&*MANIFEST_DIR)
+}
+
+/// Checks whether the input path itself is absolute, without joining it to the CARGO_MANIFEST_DIR. Returns a `bool`.
+///
+/// Multiple components can be input by using commas to separate them.
+#[proc_macro]
+pub fn is_absolute(input: TokenStream) -> TokenStream {
+    let original_path: PathBuf = parse_macro_input!(input as JoinBuilderNoBeautify).into();
+
+    let result = original_path.is_absolute();
+
+    let code = quote! {
+        #result
+    };
+
+    code.into()
+}
+
+/// Checks whether the input path itself is relative, without joining it to the CARGO_MANIFEST_DIR. Returns a `bool`. Note that `is_relative!("foo")` is `true` even though `path!("foo")` returns an absolute path.
+///
+/// Multiple components can be input by using commas to separate them.
+#[proc_macro]
+pub fn is_relative(input: TokenStream) -> TokenStream {
+    let original_path: PathBuf = parse_macro_input!(input as JoinBuilderNoBeautify).into();
+
+    let result = original_path.is_relative();
+
+    let code = quote! {
+        #result
+    };
+
+    code.into()
+}
+
+/// Returns the name of the current package, which is read from the `CARGO_PKG_NAME` environment variable when the macro is expanded. No argument is accepted.
+#[proc_macro]
+pub fn package_name(input: TokenStream) -> TokenStream {
+    if !input.is_empty() {
+        return compile_error("`package_name!` does not accept any argument");
+    }
+
+    match env::var("CARGO_PKG_NAME") {
+        Ok(name) => output_os_str(name),
+        Err(_) => compile_error("The CARGO_PKG_NAME environment variable is not set"),
+    }
+}
+
+/// Returns the version of the current package, which is read from the `CARGO_PKG_VERSION` environment variable when the macro is expanded. No argument is accepted.
+#[proc_macro]
+pub fn package_version(input: TokenStream) -> TokenStream {
+    if !input.is_empty() {
+        return compile_error("`package_version!` does not accept any argument");
+    }
+
+    match env::var("CARGO_PKG_VERSION") {
+        Ok(version) => output_os_str(version),
+        Err(_) => compile_error("The CARGO_PKG_VERSION environment variable is not set"),
+    }
+}
+
+/// Allows input a primary path and a fallback path. Each of them can be an absolute path, or a relative path. If a relative path is input, it will be relative to the CARGO_MANIFEST_DIR (a directory where your `Cargo.toml` located). Returns the absolute primary path if it exists, or the absolute fallback path otherwise. The existence of the fallback path is not checked.
+///
+/// Multiple components of the primary path or the fallback path can be grouped by parentheses, e.g. `path_if_exists_else!(("config", "local.toml"), "config.toml")`.
+#[proc_macro]
+pub fn path_if_exists_else(input: TokenStream) -> TokenStream {
+    let jb = parse_macro_input!(input as JoinBuilderPair);
+
+    let p = if jb.0.is_absolute() { jb.0 } else { MANIFEST_DIR.join(jb.0) };
+
+    if p.exists() {
+        output_path(p)
+    } else {
+        let fallback = if jb.1.is_absolute() { jb.1 } else { MANIFEST_DIR.join(jb.1) };
+
+        output_path(fallback)
+    }
+}
+
+/// Allows input an absolute path, or a relative path. If a relative path is input, it will be relative to the CARGO_MANIFEST_DIR (a directory where your `Cargo.toml` located). The path must be a directory. Returns a `&[&str]` slice of the absolute paths of the entries directly in the directory, sorted by their paths.
+///
+/// With the `ignore` feature, the entries which are ignored by the `.gitignore` files (and `.ignore` files) can be excluded by adding the leading `respect_gitignore` flag. The ignore files in the ancestors of the directory are respected too. Hidden entries, whose names start with `.` (such as `.git`), are excluded as well.
+///
+/// Multiple components can be input by using commas to separate them.
+#[proc_macro]
+pub fn read_dir(input: TokenStream) -> TokenStream {
+    let jb = parse_macro_input!(input as JoinBuilderWithGitignore);
+
+    let p = if jb.0.is_absolute() { jb.0 } else { MANIFEST_DIR.join(jb.0) };
+
+    if !p.is_dir() {
+        return compile_error_not_directory(p);
+    }
+
+    #[cfg(feature = "ignore")]
+    if jb.1 {
+        return match read_dir_not_ignored(&p, Some(1)) {
+            Ok(entries) => output_path_slice(&entries),
+            Err(err) => compile_error(format!("The path {:?} cannot be read: {}", p, err)),
+        };
+    }
+
+    let entries = match p.read_dir().and_then(|entries| {
+        entries.map(|entry| entry.map(|entry| entry.path())).collect::<std::io::Result<Vec<_>>>()
+    }) {
+        Ok(mut entries) => {
+            entries.sort();
+
+            entries
+        },
+        Err(err) => return compile_error_io(p, err),
+    };
+
+    output_path_slice(&entries)
+}
+
+/// Allows input an absolute path, or a relative path. If a relative path is input, it will be relative to the CARGO_MANIFEST_DIR (a directory where your `Cargo.toml` located). Reads the file and returns its contents as a `[u8; N]` array, whose length is known in the type.
+///
+/// To keep the compilation fast, the file must not be larger than 1 MiB. The limit (in bytes) can be changed by the leading `max_size = <size>` option.
+///
+/// The output is a block expression which also includes the file by `include_bytes!`, so that the code is recompiled when the file changes.
+///
+/// Multiple components can be input by using commas to separate them.
+#[proc_macro]
+pub fn byte_array(input: TokenStream) -> TokenStream {
+    let jb = parse_macro_input!(input as JoinBuilderWithMaxSize);
+
+    let p = if jb.0.is_absolute() { jb.0 } else { MANIFEST_DIR.join(jb.0) };
+
+    let metadata = match p.metadata() {
+        Ok(metadata) if metadata.is_file() => metadata,
+        _ => return compile_error_not_file(p),
+    };
+
+    if metadata.len() > jb.1 {
+        return compile_error(format!(
+            "The file {:?} is {} bytes, which is larger than the limit of {} bytes",
+            p,
+            metadata.len(),
+            jb.1
+        ));
+    }
+
+    match std::fs::read(&p) {
+        Ok(data) => {
+            let array = if data.is_empty() {
+                quote! {
+                    [0u8; 0]
+                }
+            } else {
+                quote! {
+                    [#(#data),*]
+                }
+            };
+
+            output_tracked(&p, array)
+        },
+        Err(err) => compile_error_io(p, err),
+    }
+}
+
+/// Allows input an absolute path, or a relative path. If a relative path is input, it will be relative to the CARGO_MANIFEST_DIR (a directory where your `Cargo.toml` located). Returns an absolute path whose last file extension is removed, e.g. `trim_extension!("src/lib.rs")` returns the path of `src/lib`. The parent directories are kept, unlike `get_file_stem!`. If there is no file extension, the path is returned as it is.
+///
+/// Multiple components can be input by using commas to separate them.
+#[proc_macro]
+pub fn trim_extension(input: TokenStream) -> TokenStream {
+    let original_path: PathBuf = parse_macro_input!(input as JoinBuilder).into();
+
+    let p =
+        if original_path.is_absolute() { original_path } else { MANIFEST_DIR.join(original_path) };
+
+    output_path(p.with_extension(""))
+}
+
+/// The same as `get_file_name!`, but returns a `&OsStr` instead of a `&str`. If the default value is not a literal string, it should be a `&OsStr`.
+///
+/// Multiple components can be input by using commas to separate them.
+#[proc_macro]
+pub fn get_file_name_os(input: TokenStream) -> TokenStream {
+    let jb = parse_macro_input!(input as JoinBuilderNoBeautifyWithDefaultValue);
+
+    match jb.0.file_name() {
+        Some(file_name) => output_os_str_typed(file_name),
+        None => match jb.1 {
+            Some(DefaultValue::LitStr(s)) => output_os_str_typed(s),
+            Some(DefaultValue::Expr(expr)) => output_expr(&expr),
+            None => compile_error(format!("The path {:?} has no file name", jb.0)),
+        },
+    }
+}
+
+#[cfg(feature = "glob")]
+/// Allows input an absolute glob pattern, or a relative glob pattern. If a relative pattern is input, it will be relative to the CARGO_MANIFEST_DIR (a directory where your `Cargo.toml` located). Returns the number of the paths (including directories) which match the pattern as a `usize`.
+///
+/// With the `ignore` feature, the paths which are ignored by the `.gitignore` files (and `.ignore` files) can be excluded by adding the leading `respect_gitignore` flag. Hidden paths, whose names start with `.`, are excluded as well.
+///
+/// Multiple components can be input by using commas to separate them.
+#[proc_macro]
+pub fn glob_count(input: TokenStream) -> TokenStream {
+    let jb = parse_macro_input!(input as JoinBuilderWithGitignore);
+
+    match glob_paths(&jb.0, jb.1) {
+        Ok(paths) => output_usize(paths.len()),
+        Err(err) => compile_error(err),
+    }
+}
+
+/// Returns the input path as it is, without joining it to the CARGO_MANIFEST_DIR. It is useful for testing the path logic independently of the build environment.
+///
+/// Multiple components can be input by using commas to separate them.
+#[proc_macro]
+pub fn raw_path(input: TokenStream) -> TokenStream {
+    let original_path: PathBuf = parse_macro_input!(input as JoinBuilder).into();
+
+    output_path(original_path)
+}
+
+/// The same as `raw_path!`, but the path must exist. A relative path is checked against the current working directory of the compiler.
+///
+/// Multiple components can be input by using commas to separate them.
+#[proc_macro]
+pub fn exist_raw_path(input: TokenStream) -> TokenStream {
+    let JoinBuilderWithMessage(original_path, msg, spans) =
+        parse_macro_input!(input as JoinBuilderWithMessage);
+
+    if original_path.exists() {
+        output_path(original_path)
+    } else {
+        compile_error_with_message(
+            msg.as_deref(),
+            spans.error_span(),
+            original_path,
+            compile_error_not_exist,
+        )
+    }
+}
+
+/// The same as `raw_path!`, but the path must be an existing directory. A relative path is checked against the current working directory of the compiler.
+///
+/// Multiple components can be input by using commas to separate them.
+#[proc_macro]
+pub fn directory_raw_path(input: TokenStream) -> TokenStream {
+    let JoinBuilderWithMessage(original_path, msg, spans) =
+        parse_macro_input!(input as JoinBuilderWithMessage);
+
+    if original_path.is_dir() {
+        output_path(original_path)
+    } else {
+        compile_error_with_message(
+            msg.as_deref(),
+            spans.error_span(),
+            original_path,
+            compile_error_not_directory,
+        )
+    }
+}
+
+/// The same as `raw_path!`, but the path must be an existing file. A relative path is checked against the current working directory of the compiler.
+///
+/// Multiple components can be input by using commas to separate them.
+#[proc_macro]
+pub fn file_raw_path(input: TokenStream) -> TokenStream {
+    let JoinBuilderWithMessage(original_path, msg, spans) =
+        parse_macro_input!(input as JoinBuilderWithMessage);
+
+    if original_path.is_file() {
+        output_path(original_path)
+    } else {
+        compile_error_with_message(
+            msg.as_deref(),
+            spans.error_span(),
+            original_path,
+            compile_error_not_file,
+        )
+    }
+}
+
+/// Gets the file extension for other purposes. If there is no file extension, an empty string is returned.
+///
+/// Multiple components can be input by using commas to separate them.
+#[proc_macro]
+pub fn extension_or_empty(input: TokenStream) -> TokenStream {
+    let original_path: PathBuf = parse_macro_input!(input as JoinBuilderNoBeautify).into();
+
+    match original_path.extension() {
+        Some(extension) => output_os_str(extension),
+        None => output_os_str(""),
+    }
+}
+
+/// Allows input an absolute path, or a relative path. If a relative path is input, it will be relative to the CARGO_MANIFEST_DIR (a directory where your `Cargo.toml` located). Returns an absolute path, and it must not be a directory. Unlike `not_directory_path!`, the path is not required to exist.
+///
+/// A custom error message can be set by the leading `msg = "<message>"` option, where `{}` is replaced with the resolved path.
+///
+/// Multiple components can be input by using commas to separate them.
+#[proc_macro]
+pub fn not_existing_directory_path(input: TokenStream) -> TokenStream {
+    let JoinBuilderWithMessage(original_path, msg, spans) =
+        parse_macro_input!(input as JoinBuilderWithMessage);
+
+    let p =
+        if original_path.is_absolute() { original_path } else { MANIFEST_DIR.join(original_path) };
+
+    match p.metadata() {
+        Ok(metadata) if metadata.is_dir() => compile_error_with_message(
+            msg.as_deref(),
+            spans.error_span(),
+            p,
+            compile_error_directory,
+        ),
+        _ => output_path(p),
+    }
+}
+
+/// Gets the first component of the input path itself, which is not joined to the CARGO_MANIFEST_DIR. If the path is empty, the default value will be used, or a compile error will be shown.
+///
+/// The prefix (on Windows) and the root directory are components too, e.g. `first_component!("/usr/lib")` returns `"/"`. They are skipped if the leading `skip_root` flag is added.
+///
+/// Multiple components can be input by using commas to separate them.
+#[proc_macro]
+pub fn first_component(input: TokenStream) -> TokenStream {
+    let jb = parse_macro_input!(input as JoinBuilderNoBeautifyWithSkipRootAndDefaultValue);
+
+    let component =
+        jb.0.components()
+            .find(|c| !jb.1 || !matches!(c, Component::Prefix(_) | Component::RootDir));
+
+    match component {
+        Some(component) => output_os_str(component),
+        None => match jb.2 {
+            Some(default_value) => output_default_os_str(&default_value),
+            None => compile_error(format!("The path {:?} has no components", jb.0)),
+        },
+    }
+}
+
+/// Gets the last component of the input path itself, which is not joined to the CARGO_MANIFEST_DIR. Unlike `get_file_name!`, the last component can be `..` or the root directory. If the path is empty, the default value will be used, or a compile error will be shown.
+///
+/// Multiple components can be input by using commas to separate them.
+#[proc_macro]
+pub fn last_component(input: TokenStream) -> TokenStream {
+    let jb = parse_macro_input!(input as JoinBuilderNoBeautifyWithDefaultValue);
+
+    match jb.0.components().next_back() {
+        Some(component) => output_os_str(component),
+        None => match jb.1 {
+            Some(default_value) => output_default_os_str(&default_value),
+            None => compile_error(format!("The path {:?} has no components", jb.0)),
+        },
+    }
+}
+
+#[cfg(feature = "hash")]
+/// Allows input an absolute path, or a relative path. If a relative path is input, it will be relative to the CARGO_MANIFEST_DIR (a directory where your `Cargo.toml` located). The path must be a directory. Returns the lowercase hex digest of all the files in the directory and its subdirectories as a `&'static str`.
+///
+/// The files are hashed in the order of their paths, along with their paths relative to the directory, so the digest only depends on the names and the contents of the files. Empty directories are not taken into account. Symbolic links to files are followed, while symbolic links to directories are skipped.
+///
+/// SHA-256 is used by default. The algorithm can be chosen by the leading `algo = "sha256"` or `algo = "sha1"` option. The recursion can be limited by the leading `max_depth = <usize>` option, which comes after the `algo` option. The directory itself is at depth `0`, and the files directly in it are at depth `1`.
+///
+/// The output is a block expression which also includes the files by `include_bytes!`, so that the code is recompiled when any of the files changes. Adding or removing files is not tracked.
+///
+/// Multiple components can be input by using commas to separate them.
+#[proc_macro]
+pub fn tree_hash(input: TokenStream) -> TokenStream {
+    let jb = parse_macro_input!(input as JoinBuilderWithHashAlgorithmAndMaxDepth);
+
+    let p = if jb.0.is_absolute() { jb.0 } else { MANIFEST_DIR.join(jb.0) };
+
+    if !p.is_dir() {
+        return compile_error_not_directory(p);
+    }
+
+    let files = match read_dir_files(&p, jb.2) {
+        Ok(files) => files,
+        Err(err) => return compile_error_io(p, err),
+    };
+
+    let hash = match hash_tree(&p, &files, jb.1) {
+        Ok(hash) => hash,
+        Err(err) => return err,
+    };
+
+    let mut file_strs = Vec::with_capacity(files.len());
+
+    for file in files.iter() {
+        match path_to_utf8_str(file) {
+            Ok(file) => file_strs.push(file),
+            Err(err) => return err,
+        }
+    }
+
+    let code = quote! {
+        {
+            #(const _: &[u8] = include_bytes!(#file_strs);)*
+
+            #hash
+        }
+    };
+
+    code.into()
+}
+
+#[cfg(feature = "mime_guess")]
+/// Allows input an absolute path, or a relative path. If a relative path is input, it will be relative to the CARGO_MANIFEST_DIR (a directory where your `Cargo.toml` located). The path must be an existing directory. Returns a `&[(&str, &str)]` slice of the files directly in the directory, sorted by their file names. Each element is a tuple of the file name and its guessed mime type.
+///
+/// If the mime type of a file cannot be guessed, the default value will be used, or a compile error will be shown. Add a leading `skip_unknown` flag to omit such files instead.
+///
+/// Multiple components can be input by using commas to separate them.
+#[proc_macro]
+pub fn mime_map(input: TokenStream) -> TokenStream {
+    let jb = parse_macro_input!(input as JoinBuilderWithSkipUnknownAndDefaultValue);
+
+    let p = if jb.0.is_absolute() { jb.0 } else { MANIFEST_DIR.join(jb.0) };
+
+    if !p.is_dir() {
+        return compile_error_not_directory(p);
+    }
+
+    let files = match read_dir_files(&p, Some(1)) {
+        Ok(files) => files,
+        Err(err) => return compile_error_io(p, err),
+    };
+
+    let mut pairs = Vec::with_capacity(files.len());
+
+    for file in files.iter() {
+        let file_name =
+            match file.file_name().map(|file_name| path_to_utf8_str(Path::new(file_name))) {
+                Some(Ok(file_name)) => file_name,
+                Some(Err(err)) => return err,
+                None => continue,
+            };
+
+        match guess_mimes(file).first() {
+            Some(mime) => {
+                let mime = mime.to_string();
+
+                pairs.push(quote! { (#file_name, #mime) });
+            },
+            None => {
+                if jb.1 {
+                    continue;
+                }
+
+                match jb.2.as_ref() {
+                    Some(default_value) => pairs.push(quote! { (#file_name, #default_value) }),
+                    None => {
+                        return compile_error(format!(
+                            "The path {:?} can not be guessed for its mime type",
+                            file
+                        ))
+                    },
+                }
+            },
+        }
+    }
+
+    let code = quote! {
+        &[#(#pairs),*] as &[(&str, &str)]
+    };
+
+    code.into()
+}
+
+/// Allows input a file extension, or a bracketed list of file extensions like `["png", "jpg"]`, followed by an absolute path or a relative path. If a relative path is input, it will be relative to the CARGO_MANIFEST_DIR (a directory where your `Cargo.toml` located). Returns an absolute path, and its file extension must be one of the given ones.
+///
+/// The comparison is case-sensitive unless the leading `ignore_case` flag is added.
+///
+/// Multiple components can be input by using commas to separate them.
+#[proc_macro]
+pub fn assert_extension(input: TokenStream) -> TokenStream {
+    let jb = parse_macro_input!(input as JoinBuilderWithExtensions);
+
+    let p = if jb.0.is_absolute() { jb.0 } else { MANIFEST_DIR.join(jb.0) };
+
+    let matched = match p.extension() {
+        Some(extension) => jb.1.iter().any(|expected_extension| {
+            let expected_extension = OsStr::new(expected_extension);
+
+            if jb.2 {
+                eq_ignore_case(extension, expected_extension)
+            } else {
+                extension == expected_extension
+            }
+        }),
+        None => false,
+    };
+
+    if matched {
+        output_path(p)
+    } else {
+        compile_error(format!(
+            "The path {:?} does not have any of the file extensions {:?}",
+            p, jb.1
+        ))
+    }
+}
+
+/// Allows input an absolute path, or a relative path. If a relative path is input, it will be relative to the CARGO_MANIFEST_DIR (a directory where your `Cargo.toml` located). Every `{target_os}` and `{target_arch}` in the path is replaced with the target operating system and the target architecture, and an absolute path is returned.
+///
+/// The possible values are given by the leading `os = ["<target_os>", ...]` and `arch = ["<target_arch>", ...]` options, which are required if the path contains the corresponding placeholder, e.g. `os_path!(os = ["linux", "macos", "windows"], "assets", "{target_os}", "icon.png")`. A path is made for every value, and the one for the target is selected by `#[cfg]` attributes, so cross-compiling is supported. Compiling for a target which is not listed shows a compile error.
+///
+/// Unless the path contains no placeholders, the output is a block expression rather than a literal, so it cannot be nested in `concat!`. It can still be used in `const` items.
+///
+/// ```compile_fail
+/// #[macro_use] extern crate manifest_dir_macros;
+///
+/// println!("{}", os_path!(os = ["an-os-which-does-not-exist"], "assets", "{target_os}"));
+/// ```
+///
+/// Multiple components can be input by using commas to separate them.
+#[proc_macro]
+pub fn os_path(input: TokenStream) -> TokenStream {
+    let jb = parse_macro_input!(input as JoinBuilderWithTargets);
+
+    let p = if jb.0.is_absolute() { jb.0 } else { MANIFEST_DIR.join(jb.0) };
+
+    let s = match path_to_utf8_str(&p) {
+        Ok(s) => s.to_string(),
+        Err(err) => return err,
+    };
+
+    // Each path is paired with the `key = "value"` predicates of its target.
+    let mut targets = vec![(Vec::new(), s)];
+
+    for (placeholder, key, option, values) in
+        [("{target_os}", "target_os", "os", &jb.1), ("{target_arch}", "target_arch", "arch", &jb.2)]
+    {
+        if !targets[0].1.contains(placeholder) {
+            continue;
+        }
+
+        if values.is_empty() {
+            return compile_error(format!(
+                "The path {:?} contains `{}`, so the leading `{} = [...]` option is required",
+                p, placeholder, option
+            ));
+        }
+
+        let key = syn::Ident::new(key, proc_macro2::Span::call_site());
+
+        let mut expanded_targets = Vec::with_capacity(targets.len() * values.len());
+
+        for (predicates, s) in targets {
+            for value in values {
+                let mut predicates = predicates.clone();
+
+                predicates.push(quote! { #key = #value });
+
+                expanded_targets.push((predicates, s.replace(placeholder, value)));
+            }
+        }
+
+        targets = expanded_targets;
+    }
+
+    if targets[0].0.is_empty() {
+        return output_path(p);
+    }
+
+    let predicates: Vec<_> =
+        targets.iter().map(|(predicates, _)| quote! { all(#(#predicates),*) }).collect();
+    let paths = targets.iter().map(|(_, s)| s);
+
+    let message = format!("The target is not any of the targets given to `os_path!` for {:?}", p);
+
+    let code = quote! {
+        {
+            #(
+                #[cfg(#predicates)]
+                const OS_PATH: &str = #paths;
+            )*
+
+            #[cfg(not(any(#(#predicates),*)))]
+            const OS_PATH: &str = ::core::compile_error!(#message);
+
+            OS_PATH
+        }
+    };
+
+    code.into()
+}
+
+/// Allows input an absolute path, or a relative path. If a relative path is input, it will be relative to the CARGO_MANIFEST_DIR (a directory where your `Cargo.toml` located). Returns a `(&'static str, &'static str)` tuple of the absolute path whose last file extension is removed and the file extension, which is the same as the results of `trim_extension!` and `extension_or_empty!`.
+///
+/// Multiple components can be input by using commas to separate them.
+#[proc_macro]
+pub fn split_extension(input: TokenStream) -> TokenStream {
+    let original_path: PathBuf = parse_macro_input!(input as JoinBuilder).into();
+
+    let p =
+        if original_path.is_absolute() { original_path } else { MANIFEST_DIR.join(original_path) };
+
+    let extension = match p.extension().map(|extension| path_to_utf8_str(Path::new(extension))) {
+        Some(Ok(extension)) => extension,
+        Some(Err(err)) => return err,
+        None => "",
+    };
+
+    let p = p.with_extension("");
+
+    let p = match path_to_utf8_str(&p) {
+        Ok(p) => p,
+        Err(err) => return err,
+    };
+
+    let code = quote! {
+        (#p, #extension)
+    };
+
+    code.into()
+}
+
+#[cfg(feature = "glob")]
+/// Allows input an absolute glob pattern, or a relative glob pattern. If a relative pattern is input, it will be relative to the CARGO_MANIFEST_DIR (a directory where your `Cargo.toml` located). Reads every file which matches the pattern, which must be a UTF-8 text file, and returns a `&[&str]` slice of their contents sorted by their paths. Directories are skipped.
+///
+/// With the `ignore` feature, the paths which are ignored by the `.gitignore` files (and `.ignore` files) can be excluded by adding the leading `respect_gitignore` flag. Hidden paths, whose names start with `.`, are excluded as well.
+///
+/// The output is a block expression which also includes the files by `include_bytes!`, so that the code is recompiled when any of the files changes. Adding or removing files is not tracked.
+///
+/// Multiple components can be input by using commas to separate them.
+#[proc_macro]
+pub fn glob_include_str(input: TokenStream) -> TokenStream {
+    let jb = parse_macro_input!(input as JoinBuilderWithGitignore);
+
+    let paths = match glob_paths(&jb.0, jb.1) {
+        Ok(paths) => paths,
+        Err(err) => return compile_error(err),
+    };
+
+    let mut file_strs = Vec::with_capacity(paths.len());
+    let mut contents = Vec::with_capacity(paths.len());
+
+    for p in paths.iter().filter(|p| p.is_file()) {
+        match path_to_utf8_str(p) {
+            Ok(file_str) => file_strs.push(file_str),
+            Err(err) => return err,
+        }
+
+        match std::fs::read_to_string(p) {
+            Ok(content) => contents.push(content),
+            Err(err) => return compile_error_io(p, err),
+        }
+    }
+
+    let code = quote! {
+        {
+            #(const _: &[u8] = include_bytes!(#file_strs);)*
+
+            &[#(#contents),*] as &[&str]
+        }
+    };
+
+    code.into()
+}
+
+/// Allows input two absolute paths or relative paths. If a relative path is input, it will be relative to the CARGO_MANIFEST_DIR (a directory where your `Cargo.toml` located). Checks whether the two paths point to the same location after normalizing them lexically, and returns a `bool`. The file system is not accessed, so the paths do not need to exist and symbolic links are not resolved.
+///
+/// Add a leading `ignore_case` flag to compare the paths case-insensitively.
+///
+/// Multiple components of a path can be grouped by parentheses, e.g. `path_eq!("src", ("src", "..", "src"))`.
+#[proc_macro]
+pub fn path_eq(input: TokenStream) -> TokenStream {
+    let jb = parse_macro_input!(input as JoinBuilderPairWithIgnoreCase);
+
+    let a = normalize_path(&if jb.0.is_absolute() { jb.0 } else { MANIFEST_DIR.join(jb.0) });
+    let b = normalize_path(&if jb.1.is_absolute() { jb.1 } else { MANIFEST_DIR.join(jb.1) });
+
+    let result = if jb.2 {
+        a.to_string_lossy().to_lowercase() == b.to_string_lossy().to_lowercase()
+    } else {
+        a == b
+    };
+
+    let code = quote! {
+        #result
+    };
+
+    code.into()
+}
+
+/// Allows input the name of an environment variable as a literal string, followed by an absolute path or a relative path. If a relative path is input, it will be relative to the directory given by the environment variable if it is set and not empty, or the CARGO_MANIFEST_DIR (a directory where your `Cargo.toml` located) otherwise. Returns the absolute path.
+///
+/// Unlike the `base = "<name>"` option, no error is shown if the environment variable is not set. Note that the environment variable is read when the macro is expanded at compile time, not when the program runs.
+///
+/// Multiple components can be input by using commas to separate them.
+#[proc_macro]
+pub fn env_or_manifest_path(input: TokenStream) -> TokenStream {
+    let jb = parse_macro_input!(input as JoinBuilderWithEnvName);
+
+    let p = if jb.1.is_absolute() {
+        jb.1
+    } else {
+        match std::env::var_os(&jb.0) {
+            Some(dir) if !dir.is_empty() => PathBuf::from(dir).join(jb.1),
+            _ => MANIFEST_DIR.join(jb.1),
+        }
+    };
+
+    output_path(p)
+}
+
+/// Gets the parent of the input path like the `dirname` command, without joining it to the CARGO_MANIFEST_DIR. Unlike `get_parent!`, there is always a result. A path without a parent directory, such as `"lib.rs"`, gives `"."`, and the root directory gives itself.
+///
+/// Multiple components can be input by using commas to separate them.
+#[proc_macro]
+pub fn dirname(input: TokenStream) -> TokenStream {
+    let original_path: PathBuf = parse_macro_input!(input as JoinBuilder).into();
+
+    match original_path.parent() {
+        Some(parent) if parent.as_os_str().is_empty() => output_path("."),
+        Some(parent) => output_path(parent),
+        None if original_path.has_root() => output_path(original_path),
+        None => output_path("."),
+    }
+}
+
+/// Gets the last component of the input path like the `basename` command. Unlike `get_file_name!`, there is always a result. The root directory gives itself, and `".."` gives `".."`.
+///
+/// A literal string can be input after the path as a suffix to be removed, e.g. `basename!("src/lib.rs", ".rs")` returns `"lib"`. The suffix is not removed if it is the whole name.
+///
+/// Multiple components of the path can be grouped by parentheses, e.g. `basename!(("src", "lib.rs"), ".rs")`.
+#[proc_macro]
+pub fn basename(input: TokenStream) -> TokenStream {
+    let jb = parse_macro_input!(input as JoinBuilderNoBeautifyWithSuffix);
+
+    let name = match jb.0.components().next_back() {
+        Some(component) => component.as_os_str(),
+        None => OsStr::new(""),
+    };
+
+    match (name.to_str(), jb.1) {
+        (Some(name), Some(suffix)) if name != suffix && name.ends_with(suffix.as_str()) => {
+            output_os_str(&name[..name.len() - suffix.len()])
+        },
+        _ => output_os_str(name),
+    }
+}
+
+/// Allows input an absolute path, or a relative path. If a relative path is input, it will be relative to the CARGO_MANIFEST_DIR (a directory where your `Cargo.toml` located). Returns the canonical absolute path, with symbolic links resolved, if the path can be canonicalized. Otherwise, such as when the path does not exist, returns the absolute path without canonicalizing it instead of showing a compile error.
+///
+/// Note that the result depends on whether the path exists when the macro is expanded at compile time. On Windows, a canonical path is a verbatim path starting with `\\?\`.
+///
+/// Multiple components can be input by using commas to separate them.
+#[proc_macro]
+pub fn try_canonicalize_path(input: TokenStream) -> TokenStream {
+    let original_path: PathBuf = parse_macro_input!(input as JoinBuilder).into();
+
+    let p =
+        if original_path.is_absolute() { original_path } else { MANIFEST_DIR.join(original_path) };
+
+    match p.canonicalize() {
+        Ok(canonical_path) => output_path(canonical_path),
+        Err(_) => output_path(p),
+    }
+}
+
+/// The same as `path!`, but returns a `String` instead of a `&str`.
+///
+/// Multiple components can be input by using commas to separate them.
+#[proc_macro]
+pub fn path_string(input: TokenStream) -> TokenStream {
+    output_string(path(input))
+}
+
+/// The same as `get_file_name!`, but returns a `String` instead of a `&str`. The default value must be a `&str` as well.
+///
+/// Multiple components can be input by using commas to separate them.
+#[proc_macro]
+pub fn file_name_string(input: TokenStream) -> TokenStream {
+    output_string(get_file_name(input))
+}
+
+/// The same as `get_file_stem!`, but returns a `String` instead of a `&str`. The default value must be a `&str` as well.
+///
+/// Multiple components can be input by using commas to separate them.
+#[proc_macro]
+pub fn file_stem_string(input: TokenStream) -> TokenStream {
+    output_string(get_file_stem(input))
+}
+
+/// The same as `get_extension!`, but returns a `String` instead of a `&str`. The default value must be a `&str` as well.
+///
+/// Multiple components can be input by using commas to separate them.
+#[proc_macro]
+pub fn extension_string(input: TokenStream) -> TokenStream {
+    output_string(get_extension(input))
+}
+
+/// The same as `get_parent!`, but returns a `String` instead of a `&str`. The default value must be a `&str` as well.
+///
+/// Multiple components can be input by using commas to separate them.
+#[proc_macro]
+pub fn parent_string(input: TokenStream) -> TokenStream {
+    output_string(get_parent(input))
+}
+
+/// Allows input an absolute path, or a relative path. If a relative path is input, it will be relative to the CARGO_MANIFEST_DIR (a directory where your `Cargo.toml` located). Normalizes the path lexically and returns the number of levels below the CARGO_MANIFEST_DIR where the path lands, as an `isize`. If the path is not in the CARGO_MANIFEST_DIR, the result is negative, and its absolute value is the number of `..` components needed to leave the CARGO_MANIFEST_DIR, e.g. `relative_depth!("a/../../x")` returns `-1`. The file system is not accessed.
+///
+/// Multiple components can be input by using commas to separate them.
+#[proc_macro]
+pub fn relative_depth(input: TokenStream) -> TokenStream {
+    let original_path: PathBuf = parse_macro_input!(input as JoinBuilderNoBeautify).into();
+
+    let p =
+        if original_path.is_absolute() { original_path } else { MANIFEST_DIR.join(original_path) };
+
+    let relative_path = relative_path_between(&normalize_path(&MANIFEST_DIR), &normalize_path(&p));
+
+    let mut components = relative_path.components().peekable();
+
+    let mut depth = 0isize;
+
+    while let Some(Component::ParentDir) = components.peek() {
+        components.next();
+
+        depth -= 1;
+    }
+
+    if depth == 0 {
+        depth = components.filter(|component| matches!(component, Component::Normal(_))).count()
+            as isize;
+    }
+
+    output_isize(depth)
+}
+
+/// Allows input multiple absolute paths or relative paths. If a relative path is input, it will be relative to the CARGO_MANIFEST_DIR (a directory where your `Cargo.toml` located). Normalizes the paths lexically, removes the duplicates while keeping the first occurrence of each path in order, and returns a `&[&str]` slice of them. The file system is not accessed.
+///
+/// Multiple components of a path can be grouped by parentheses, e.g. `unique_paths!("src", ("src", "..", "src"), "examples")`.
+#[proc_macro]
+pub fn unique_paths(input: TokenStream) -> TokenStream {
+    let jb = parse_macro_input!(input as JoinBuilderList);
+
+    let mut paths: Vec<PathBuf> = Vec::with_capacity(jb.0.len());
+
+    for original_path in jb.0 {
+        let p = normalize_path(&if original_path.is_absolute() {
+            original_path
+        } else {
+            MANIFEST_DIR.join(original_path)
+        });
+
+        if !paths.contains(&p) {
+            paths.push(p);
+        }
+    }
+
+    output_path_slice(&paths)
+}
+
+#[cfg(feature = "mime_guess")]
+/// The same as `mime_guess!`, but returns a `mime::Mime` instead of a `&str`. The crate which uses this macro needs to depend on the `mime` crate with the version `0.3`, which is the one used by `mime_guess` 2, such as `mime = "0.3"` in its `Cargo.toml`.
+///
+/// A constant of the `mime` crate, such as `mime::TEXT_CSS`, is returned if there is one for the mime type. Otherwise, the mime type is parsed by `str::parse` at runtime. A literal string as the default value is validated at compile time and then output in the same way, and any other expression as the default value must be a `mime::Mime`.
+///
+/// ```compile_fail
+/// #[macro_use] extern crate manifest_dir_macros;
+///
+/// let mime: mime::Mime = mime_guess_typed!(default = "not a mime", "LICENSE");
+/// ```
+///
+/// Multiple components can be input by using commas to separate them.
+#[proc_macro]
+pub fn mime_guess_typed(input: TokenStream) -> TokenStream {
+    let jb = parse_macro_input!(input as JoinBuilderNoBeautifyWithDefaultValue);
+
+    match guess_mimes(&jb.0).first() {
+        Some(mime) => output_mime(mime),
+        None => match jb.1 {
+            Some(DefaultValue::LitStr(s)) => match s.parse::<mime_guess::Mime>() {
+                Ok(mime) => output_mime(&mime),
+                Err(err) => {
+                    compile_error(format!("The default value {:?} is not a mime type: {}", s, err))
+                },
+            },
+            Some(DefaultValue::Expr(expr)) => output_expr(&expr),
+            None => {
+                compile_error(format!("The path {:?} can not be guessed for its mime type", jb.0))
+            },
+        },
+    }
+}
+
+/// Allows input an absolute path, or a relative path, followed by a file extension as a literal string. If a relative path is input, it will be relative to the CARGO_MANIFEST_DIR (a directory where your `Cargo.toml` located). Appends the file extension to the file name, keeping the original file extension, and returns the absolute path, e.g. `add_extension!("style.css", "gz")` ends with `style.css.gz` and `add_extension!(".env", "gz")` ends with `.env.gz`. A leading `.` of the file extension is optional. If there is no file name, a compile error will be shown.
+///
+/// Multiple components can be input by using commas to separate them.
+#[proc_macro]
+pub fn add_extension(input: TokenStream) -> TokenStream {
+    let jb = parse_macro_input!(input as JoinBuilderWithTrailingValue);
+
+    let mut p = if jb.0.is_absolute() { jb.0 } else { MANIFEST_DIR.join(jb.0) };
+
+    let extension = jb.1.trim_start_matches('.');
+
+    if extension.is_empty() {
+        return output_path(p);
+    }
+
+    let file_name = match p.file_name() {
+        Some(file_name) => {
+            let mut file_name = file_name.to_os_string();
+
+            file_name.push(".");
+            file_name.push(extension);
+
+            file_name
+        },
+        None => return compile_error(format!("The path {:?} has no file name", p)),
+    };
+
+    p.set_file_name(file_name);
+
+    output_path(p)
+}
+
+/// Allows input an absolute path, or a relative path. If a relative path is input, it will be relative to the CARGO_MANIFEST_DIR (a directory where your `Cargo.toml` located). The path must be a directory. Returns the number of the entries directly in the directory as a `usize`.
+///
+/// Add a leading `files_only` flag to count only files, or a leading `dirs_only` flag to count only directories. Symbolic links are counted by what they point to.
+///
+/// Multiple components can be input by using commas to separate them.
+#[proc_macro]
+pub fn child_count(input: TokenStream) -> TokenStream {
+    let jb = parse_macro_input!(input as JoinBuilderWithEntryKind);
+
+    let p = if jb.0.is_absolute() { jb.0 } else { MANIFEST_DIR.join(jb.0) };
+
+    if !p.is_dir() {
+        return compile_error_not_directory(p);
+    }
+
+    let entries = match p.read_dir() {
+        Ok(entries) => entries,
+        Err(err) => return compile_error_io(p, err),
+    };
+
+    let mut count = 0;
+
+    for entry in entries {
+        let path = match entry {
+            Ok(entry) => entry.path(),
+            Err(err) => return compile_error_io(p, err),
+        };
+
+        let counted = match jb.1 {
+            EntryKind::All => true,
+            EntryKind::File => path.is_file(),
+            EntryKind::Directory => path.is_dir(),
+        };
+
+        if counted {
+            count += 1;
+        }
+    }
+
+    output_usize(count)
+}
+
+/// Allows input an absolute path, or a relative path. If a relative path is input, it will be relative to the CARGO_MANIFEST_DIR (a directory where your `Cargo.toml` located). The path must exist. Returns the absolute path in the short (8.3) form on Windows, such as `C:\PROGRA~1\foo`, for legacy tools which cannot handle long names.
+///
+/// A compile error will be shown if the short form cannot be obtained, such as when the generation of 8.3 names is disabled on the volume. On other platforms, the plain absolute path is returned like `path!`.
+///
+/// Multiple components can be input by using commas to separate them.
+#[proc_macro]
+pub fn short_path(input: TokenStream) -> TokenStream {
+    let original_path: PathBuf = parse_macro_input!(input as JoinBuilder).into();
+
+    let p =
+        if original_path.is_absolute() { original_path } else { MANIFEST_DIR.join(original_path) };
+
+    if !p.exists() {
+        return compile_error_not_exist(p);
+    }
+
+    #[cfg(windows)]
+    let p = match get_short_path(&p) {
+        Ok(short_path) => {
+            if short_path == p && !is_8dot3_path(&p) {
+                return compile_error(format!(
+                    "The path {:?} has no short form, the generation of 8.3 names may be disabled \
+                     on the volume",
+                    p
+                ));
+            }
+
+            short_path
+        },
+        Err(err) => {
+            return compile_error(format!(
+                "The short form of the path {:?} cannot be obtained: {}",
+                p, err
+            ))
+        },
+    };
+
+    output_path(p)
+}
+
+/// Allows input an absolute path, or a relative path. If a relative path is input, it will be relative to the CARGO_MANIFEST_DIR (a directory where your `Cargo.toml` located). The path must be a UTF-8 text file which lists paths, one per line. Empty lines and lines starting with `#` are skipped, and the leading and trailing whitespaces of each line are trimmed. Returns a `&[&str]` slice of the absolute paths in the order they are listed.
+///
+/// A relative path in the list is relative to the CARGO_MANIFEST_DIR as well. Every listed path must exist, otherwise a compile error which tells the line number will be shown.
+///
+/// The output is a block expression which also includes the list file by `include_bytes!`, so that the code is recompiled when the list changes.
+///
+/// Multiple components can be input by using commas to separate them.
+#[proc_macro]
+pub fn path_list_from_file(input: TokenStream) -> TokenStream {
+    let original_path: PathBuf = parse_macro_input!(input as JoinBuilder).into();
+
+    let p =
+        if original_path.is_absolute() { original_path } else { MANIFEST_DIR.join(original_path) };
+
+    if !p.is_file() {
+        return compile_error_not_file(p);
+    }
+
+    let content = match std::fs::read_to_string(&p) {
+        Ok(content) => content,
+        Err(err) => return compile_error_io(p, err),
+    };
+
+    let mut paths = Vec::new();
+
+    for (i, line) in content.lines().enumerate() {
+        let line = line.trim();
+
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let listed_path = Path::new(line);
+
+        let listed_path = if listed_path.is_absolute() {
+            listed_path.to_path_buf()
+        } else {
+            MANIFEST_DIR.join(listed_path)
+        };
+
+        if !listed_path.exists() {
+            return compile_error(format!(
+                "The path {:?} on line {} of {:?} does not exist",
+                listed_path,
+                i + 1,
+                p
+            ));
+        }
+
+        paths.push(listed_path);
+    }
+
+    output_tracked(&p, proc_macro2::TokenStream::from(output_path_slice(&paths)))
+}
+
+/// Allows input the name of an environment variable as a literal string. Reads the environment variable when the macro is expanded at compile time and treats its value as an absolute path, or a relative path. If a relative path is read, it will be relative to the CARGO_MANIFEST_DIR (a directory where your `Cargo.toml` located). Returns the absolute path. If the environment variable is not set or empty, the default value will be used, or a compile error will be shown.
+#[proc_macro]
+pub fn getenv_path(input: TokenStream) -> TokenStream {
+    let jb = parse_macro_input!(input as JoinBuilderEnvNameWithDefaultValue);
+
+    match std::env::var_os(&jb.0) {
+        Some(value) if !value.is_empty() => {
+            let original_path = PathBuf::from(value);
+
+            let p = if original_path.is_absolute() {
+                original_path
+            } else {
+                MANIFEST_DIR.join(original_path)
+            };
+
+            output_path(p)
+        },
+        _ => match jb.1 {
+            Some(default_value) => output_default_path(&default_value),
+            None => compile_error(format!("The {} environment variable is not set or empty", jb.0)),
+        },
+    }
+}
+
+/// The same as `getenv_path!`, but the path read from the environment variable must exist. The default value is not checked.
+#[proc_macro]
+pub fn exist_getenv_path(input: TokenStream) -> TokenStream {
+    let jb = parse_macro_input!(input as JoinBuilderEnvNameWithDefaultValue);
+
+    match std::env::var_os(&jb.0) {
+        Some(value) if !value.is_empty() => {
+            let original_path = PathBuf::from(value);
+
+            let p = if original_path.is_absolute() {
+                original_path
+            } else {
+                MANIFEST_DIR.join(original_path)
+            };
+
+            if p.exists() {
+                output_path(p)
+            } else {
+                compile_error_not_exist(p)
+            }
+        },
+        _ => match jb.1 {
+            Some(default_value) => output_default_path(&default_value),
+            None => compile_error(format!("The {} environment variable is not set or empty", jb.0)),
+        },
+    }
+}
+
+/// Gets the file extension for other purposes like `get_extension!`, but a compound extension such as `tar.gz` is returned as a whole, e.g. `compound_extension!("archive.tar.gz")` returns `"tar.gz"`. If there is no file extension, the default value will be used, or a compile error will be shown.
+///
+/// `tar.gz`, `tar.bz2`, `tar.xz` and `tar.zst` are recognized case-insensitively. More compound extensions can be added by the leading `compounds = ["<extension>", ...]` option, which comes before the `default =` option. If several of them match, the longest one is chosen.
+///
+/// Multiple components can be input by using commas to separate them.
+#[proc_macro]
+pub fn compound_extension(input: TokenStream) -> TokenStream {
+    let jb = parse_macro_input!(input as JoinBuilderNoBeautifyWithCompoundsAndDefaultValue);
+
+    if let Some(file_name) = jb.0.file_name().and_then(|file_name| file_name.to_str()) {
+        let compound =
+            jb.1.iter()
+                .filter(|compound| {
+                    file_name.len() > compound.len() + 1
+                        && file_name.is_char_boundary(file_name.len() - compound.len())
+                        && {
+                            let (stem, extension) =
+                                file_name.split_at(file_name.len() - compound.len());
+
+                            stem.ends_with('.') && extension.eq_ignore_ascii_case(compound)
+                        }
+                })
+                .max_by_key(|compound| compound.len());
+
+        if let Some(compound) = compound {
+            return output_os_str(&file_name[file_name.len() - compound.len()..]);
+        }
+    }
+
+    match jb.0.extension() {
+        Some(extension) => output_os_str(extension),
+        None => match jb.2 {
+            Some(default_value) => output_default_os_str(&default_value),
+            None => compile_error(format!("The path {:?} has no file extension", jb.0)),
+        },
+    }
+}
+
+/// Allows input an absolute path, or a relative path. If a relative path is input, it will be relative to the CARGO_MANIFEST_DIR (a directory where your `Cargo.toml` located). The path must exist. Canonicalizes the path, with symbolic links resolved, and returns it relative to the canonical CARGO_MANIFEST_DIR, e.g. `realpath_relative!("src", "..", "src", "lib.rs")` returns `"src/lib.rs"` on Unix-like platforms. The CARGO_MANIFEST_DIR itself gives `"."`.
+///
+/// If the canonical path is not in the CARGO_MANIFEST_DIR, such as when a symbolic link points to somewhere else, a compile error will be shown.
+///
+/// Multiple components can be input by using commas to separate them.
+#[proc_macro]
+pub fn realpath_relative(input: TokenStream) -> TokenStream {
+    let original_path: PathBuf = parse_macro_input!(input as JoinBuilder).into();
+
+    let p =
+        if original_path.is_absolute() { original_path } else { MANIFEST_DIR.join(original_path) };
+
+    if !p.exists() {
+        return compile_error_not_exist(p);
+    }
+
+    let canonical_path = match p.canonicalize() {
+        Ok(canonical_path) => canonical_path,
+        Err(err) => return compile_error_io(p, err),
+    };
+
+    let canonical_manifest_dir = match MANIFEST_DIR.canonicalize() {
+        Ok(canonical_manifest_dir) => canonical_manifest_dir,
+        Err(err) => return compile_error_io(&*MANIFEST_DIR, err),
+    };
+
+    match canonical_path.strip_prefix(&canonical_manifest_dir) {
+        Ok(relative_path) if relative_path.as_os_str().is_empty() => output_path("."),
+        Ok(relative_path) => output_path(relative_path),
+        Err(_) => compile_error(format!(
+            "The canonical path {:?} of the path {:?} is not in the CARGO_MANIFEST_DIR {:?}",
+            canonical_path, p, canonical_manifest_dir
+        )),
+    }
+}
+
+/// Allows input an absolute path, or a relative path. If a relative path is input, it will be relative to the CARGO_MANIFEST_DIR (a directory where your `Cargo.toml` located). Returns the absolute path without trailing path separators, e.g. `trim_trailing_separator!("src/")` ends with `src`. A root directory such as `/` or `C:\` is kept as it is.
+///
+/// Only the trailing path separators are removed, and the `.` and `..` components are kept. The file system is not accessed.
+///
+/// Multiple components can be input by using commas to separate them.
+#[proc_macro]
+pub fn trim_trailing_separator(input: TokenStream) -> TokenStream {
+    let original_path: PathBuf = parse_macro_input!(input as JoinBuilder).into();
+
+    let p =
+        if original_path.is_absolute() { original_path } else { MANIFEST_DIR.join(original_path) };
+
+    let s = match path_to_utf8_str(&p) {
+        Ok(s) => s,
+        Err(err) => return err,
+    };
+
+    let mut trimmed = s;
+
+    while let Some(last) = trimmed.chars().last() {
+        if !std::path::is_separator(last) {
+            break;
+        }
+
+        let candidate = &trimmed[..trimmed.len() - last.len_utf8()];
+
+        // removing the separator of a root, such as `/` or `C:\`, would change the meaning of the path
+        if candidate.is_empty() || !Path::new(candidate).has_root() {
+            break;
+        }
+
+        trimmed = candidate;
+    }
+
+    output_path(trimmed)
+}
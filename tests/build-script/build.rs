@@ -0,0 +1,36 @@
+use std::{
+    env,
+    fs::{self, File},
+    path::Path,
+    time::{Duration, SystemTime},
+};
+
+fn main() {
+    // Cargo only sets `PROFILE` for build scripts, so pass it to the compiler of the crate.
+    println!("cargo:rustc-env=PROFILE={}", env::var("PROFILE").unwrap());
+
+    let out_dir = env::var_os("OUT_DIR").unwrap();
+
+    let generated_dir = Path::new(&out_dir).join("generated");
+
+    fs::create_dir_all(&generated_dir).unwrap();
+    fs::write(generated_dir.join("mod.rs"), "pub const GENERATED: &str = \"generated\";\n")
+        .unwrap();
+
+    // Files with controlled modification times for `newest_in_dir!`.
+    let newest_dir = Path::new(&out_dir).join("newest");
+
+    fs::create_dir_all(newest_dir.join("sub")).unwrap();
+
+    for (name, secs) in [
+        ("a.txt", 1_000_000_000),
+        ("b.txt", 1_000_000_300),
+        ("c.txt", 1_000_000_100),
+        ("sub/d.txt", 1_000_000_600),
+        ("sub/e.txt", 1_000_000_600),
+    ] {
+        let file = File::create(newest_dir.join(name)).unwrap();
+
+        file.set_modified(SystemTime::UNIX_EPOCH + Duration::from_secs(secs)).unwrap();
+    }
+}
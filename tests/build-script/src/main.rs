@@ -0,0 +1,53 @@
+#[macro_use]
+extern crate manifest_dir_macros;
+
+use std::path::Path;
+
+mod generated {
+    include!(concat!(env!("OUT_DIR"), "/", out_dir_relative!("generated/mod.rs")));
+}
+
+fn main() {
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+
+    assert_eq!(
+        Path::new(profile_path!("assets/{profile}/config.json")),
+        manifest_dir.join("assets").join(env!("PROFILE")).join("config.json")
+    );
+    assert_eq!(
+        Path::new(profile_path!(env = "PROFILE", "assets", "{profile}")),
+        manifest_dir.join("assets").join(env!("PROFILE"))
+    );
+
+    let generated_mod = Path::new("generated").join("mod.rs");
+
+    assert_eq!(generated::GENERATED, "generated");
+    assert_eq!(Path::new(out_dir_relative!("generated/mod.rs")), generated_mod);
+    assert_eq!(
+        Path::new(out_dir_relative!("./generated", "..", "generated/mod.rs")),
+        generated_mod
+    );
+    assert_eq!(
+        Path::new(out_dir_relative!(base = "OUT_DIR", "generated", "mod.rs")),
+        generated_mod
+    );
+    assert_eq!(out_dir_relative!("generated", ".."), ".");
+
+    // `sub/d.txt` and `sub/e.txt` have the same modification time, and the first one in the sorted order is chosen.
+    const NEWEST: (&str, u64) = newest_in_dir!(base = "OUT_DIR", "newest");
+    const NEWEST_RECURSIVE: (&str, u64) = newest_in_dir!(recursive, base = "OUT_DIR", "newest");
+
+    let newest_dir = Path::new(env!("OUT_DIR")).join("newest");
+
+    assert_eq!(
+        (Path::new(NEWEST.0), NEWEST.1),
+        (newest_dir.join("b.txt").as_path(), 1_000_000_300)
+    );
+    assert_eq!(
+        (Path::new(NEWEST_RECURSIVE.0), NEWEST_RECURSIVE.1),
+        (newest_dir.join("sub").join("d.txt").as_path(), 1_000_000_600)
+    );
+
+    println!("{}", profile_path!("assets/{profile}/config.json"));
+    println!("{}", out_dir_relative!("generated/mod.rs"));
+}
@@ -0,0 +1,99 @@
+use std::{
+    env,
+    path::Path,
+    process::{Command, Output},
+};
+
+/// Runs a fixture crate in the `tests` directory by `cargo run`.
+fn cargo_run(manifest_path: &Path) -> Output {
+    // The dependencies of the fixture crates are the same as the ones of this crate, which have been fetched already.
+    Command::new(env::var_os("CARGO").unwrap_or_else(|| "cargo".into()))
+        .arg("run")
+        .arg("--offline")
+        .arg("--quiet")
+        .arg("--manifest-path")
+        .arg(manifest_path)
+        .arg("--target-dir")
+        .arg(Path::new(env!("CARGO_TARGET_TMPDIR")).join("fixture-crates"))
+        .output()
+        .unwrap()
+}
+
+/// Runs a fixture crate which must be built and run successfully, and returns its standard output and standard error. The fixture crates assert the outputs of the macros by themselves.
+fn run(manifest_path: &Path) -> (String, String) {
+    let output = cargo_run(manifest_path);
+
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+
+    (String::from_utf8(output.stdout).unwrap(), String::from_utf8(output.stderr).unwrap())
+}
+
+/// The build script of the crate in `tests/build-script` prepares the environment variables and the files for the macros which are meant to be used with a build script.
+#[test]
+fn build_script() {
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+
+    let (stdout, _) = run(&manifest_dir.join("tests").join("build-script").join("Cargo.toml"));
+
+    let mut lines = stdout.lines();
+
+    let profile_path = Path::new(lines.next().unwrap());
+
+    assert_eq!(
+        profile_path,
+        manifest_dir
+            .join("tests")
+            .join("build-script")
+            .join("assets")
+            .join("debug")
+            .join("config.json")
+    );
+
+    assert_eq!(Path::new(lines.next().unwrap()), Path::new("generated").join("mod.rs"));
+}
+
+/// The manifest of the `member` crate in `tests/workspace` mentions `[workspace]` only in comments, strings and other tables, and the manifest of the `explicit` crate sets `package.workspace`, so the root of the workspace is their parent directory, whose manifest defines the `workspace` table by dotted keys.
+#[test]
+fn workspace() {
+    let workspace_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("workspace");
+
+    for member in ["member", "explicit"] {
+        let (stdout, _) = run(&workspace_dir.join(member).join("Cargo.toml"));
+
+        assert_eq!(Path::new(stdout.trim_end()), workspace_dir.join("Cargo.toml"));
+    }
+}
+
+/// The crate in `tests/warn` uses `exist_path!(warn, ...)` on a missing path without allowing the warning, so the build succeeds, the warning is shown, and the path is still returned.
+#[test]
+fn warn() {
+    let warn_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("warn");
+
+    let (stdout, stderr) = run(&warn_dir.join("Cargo.toml"));
+
+    assert_eq!(Path::new(stdout.trim_end()), warn_dir.join("assets").join("not-added-yet.png"));
+
+    assert!(stderr.contains("deprecated"), "{}", stderr);
+    assert!(stderr.contains("not-added-yet.png\" does not exist"), "{}", stderr);
+}
+
+/// The crate in `tests/msg` uses `directory_path!` with a custom error message on a file, so the build fails with the message whose `{}` is replaced with the resolved path.
+#[test]
+fn msg() {
+    let msg_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("msg");
+
+    let output = cargo_run(&msg_dir.join("Cargo.toml"));
+
+    assert!(!output.status.success());
+
+    let stderr = String::from_utf8(output.stderr).unwrap();
+
+    assert!(
+        stderr.contains(&format!(
+            "{} must be a directory of assets",
+            msg_dir.join("Cargo.toml").display()
+        )),
+        "{}",
+        stderr
+    );
+}
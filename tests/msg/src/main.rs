@@ -0,0 +1,6 @@
+#[macro_use]
+extern crate manifest_dir_macros;
+
+fn main() {
+    println!("{}", directory_path!(msg = "{} must be a directory of assets", "Cargo.toml"));
+}
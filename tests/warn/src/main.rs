@@ -0,0 +1,10 @@
+#[macro_use]
+extern crate manifest_dir_macros;
+
+fn main() {
+    let p = exist_path!(warn, "assets", "not-added-yet.png");
+
+    assert_eq!(p, path!("assets", "not-added-yet.png"));
+
+    println!("{}", p);
+}
@@ -0,0 +1,12 @@
+#[macro_use]
+extern crate manifest_dir_macros;
+
+use std::path::Path;
+
+fn main() {
+    let workspace_dir = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+
+    assert_eq!(Path::new(path!(base = workspace, "Cargo.toml")), workspace_dir.join("Cargo.toml"));
+
+    println!("{}", path!(base = workspace, "Cargo.toml"));
+}